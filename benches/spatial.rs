@@ -0,0 +1,153 @@
+//! Compares the spatial grid with checking every entity, for the searches
+//! the game makes each frame: the hook against everything in the water, and
+//! every fish looking for its school.
+//!
+//! ```sh
+//! cargo bench --bench spatial
+//! ```
+
+use bevy::prelude::*;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use gone_fish::spatial::SpatialGrid;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// Counts to compare at, up to well past what the game spawns today.
+const COUNTS: [usize; 4] = [100, 1_000, 5_000, 20_000];
+/// Half the width of the world, matching where fish turn back.
+const WORLD_EDGE: f32 = 1800.;
+const WORLD_DEPTH: f32 = 2000.;
+const HOOK_SIZE: Vec2 = Vec2::new(24., 24.);
+const SCHOOL_RADIUS: f32 = 150.;
+
+struct Scene {
+    entities: Vec<(Entity, Vec2, Vec2)>,
+    grid: SpatialGrid<()>,
+}
+
+fn scene(count: usize) -> Scene {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let entities: Vec<_> = (0..count)
+        .map(|index| {
+            let position = Vec2::new(
+                rng.gen_range(-WORLD_EDGE..WORLD_EDGE),
+                -rng.gen_range(0.0..WORLD_DEPTH),
+            );
+            // Fish run from a 12 by 6 frame at the smallest scale up to a
+            // shark at the largest
+            let size = Vec2::new(rng.gen_range(30.0..200.), rng.gen_range(15.0..50.));
+            (Entity::from_raw(index as u32), position, size)
+        })
+        .collect();
+
+    let mut grid = SpatialGrid::default();
+    for (entity, position, size) in &entities {
+        grid.insert(*entity, *position, *size);
+    }
+
+    Scene { entities, grid }
+}
+
+fn overlaps(a: Vec2, a_size: Vec2, b: Vec2, b_size: Vec2) -> bool {
+    let offset = (a - b).abs();
+    let reach = (a_size + b_size) / 2.;
+    offset.x < reach.x && offset.y < reach.y
+}
+
+fn hook(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hook");
+    let hook = Vec2::new(0., -500.);
+
+    for count in COUNTS {
+        let scene = scene(count);
+
+        group.bench_with_input(
+            BenchmarkId::new("every entity", count),
+            &scene,
+            |b, scene| {
+                b.iter(|| {
+                    scene
+                        .entities
+                        .iter()
+                        .filter(|(_, position, size)| overlaps(hook, HOOK_SIZE, *position, *size))
+                        .count()
+                })
+            },
+        );
+        group.bench_with_input(BenchmarkId::new("grid", count), &scene, |b, scene| {
+            b.iter(|| {
+                scene
+                    .grid
+                    .nearby(hook, HOOK_SIZE)
+                    .filter(|entity| {
+                        let (_, position, size) = scene.entities[entity.index() as usize];
+                        overlaps(hook, HOOK_SIZE, position, size)
+                    })
+                    .count()
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn schools(c: &mut Criterion) {
+    let mut group = c.benchmark_group("schools");
+    // Checking every pair takes a while at the larger counts
+    group.sample_size(10);
+
+    for count in COUNTS {
+        let scene = scene(count);
+
+        group.bench_with_input(
+            BenchmarkId::new("every entity", count),
+            &scene,
+            |b, scene| {
+                b.iter(|| {
+                    let mut neighbours = 0;
+                    for (_, position, _) in &scene.entities {
+                        neighbours += scene
+                            .entities
+                            .iter()
+                            .filter(|(_, other, _)| position.distance(*other) <= SCHOOL_RADIUS)
+                            .count();
+                    }
+                    neighbours
+                })
+            },
+        );
+        group.bench_with_input(BenchmarkId::new("grid", count), &scene, |b, scene| {
+            b.iter(|| {
+                let mut neighbours = 0;
+                for (_, position, _) in &scene.entities {
+                    neighbours += scene.grid.within(*position, SCHOOL_RADIUS).count();
+                }
+                neighbours
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn rebuild(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rebuild");
+
+    for count in COUNTS {
+        let mut scene = scene(count);
+
+        group.bench_function(BenchmarkId::from_parameter(count), |b| {
+            b.iter(|| {
+                scene.grid.clear();
+                for (entity, position, size) in &scene.entities {
+                    scene.grid.insert(*entity, *position, *size);
+                }
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, hook, schools, rebuild);
+criterion_main!(benches);
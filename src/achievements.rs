@@ -0,0 +1,449 @@
+use crate::{
+    despawn_screen,
+    events::{
+        AchievementUnlockedEvent, CatchFishEvent, DepositFishEvent, TrashCollisionEvent,
+        UpgradeEvent,
+    },
+    fish::FishVariant,
+    menu::{MenuState, NORMAL_BUTTON},
+    replay::Replay,
+    resources::load_data_file,
+    save::{data_path, write_data_file},
+    GameState::Game,
+    GameplaySet, PauseState, TEXT_COLOR,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, fs};
+
+/// How long an unlock is shown for, in seconds.
+const TOAST_TIME: f32 = 4.;
+const TOAST_HEIGHT: f32 = 60.;
+const TOAST_COLOR: Color = Color::rgba(0.1, 0.1, 0.1, 0.9);
+const LOCKED_TEXT_COLOR: Color = Color::rgb(0.5, 0.5, 0.5);
+
+/// What has to happen for an achievement to unlock. Counts are over every
+/// game played.
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub enum Condition {
+    /// Land this many fish.
+    Catches(u32),
+    /// Land this many trophies.
+    Trophies(u32),
+    /// Land one of this species.
+    Species(FishVariant),
+    /// Land one of every species.
+    AllSpecies,
+    /// Land a single fish of at least this many kg.
+    FishWeight(f32),
+    /// Sell this many kg at the port over one game.
+    PortWeight(f32),
+    /// Sell this many kg at the port in one go.
+    Haul(f32),
+    /// Buy this many upgrades.
+    Upgrades(u32),
+    /// Have this many catches knocked off the line by rubbish.
+    TrashHits(u32),
+}
+
+impl Condition {
+    pub fn is_met(self, stats: &AchievementStats) -> bool {
+        match self {
+            Condition::Catches(count) => stats.catches >= count,
+            Condition::Trophies(count) => stats.trophies >= count,
+            Condition::Species(variant) => stats.species.contains(&variant),
+            Condition::AllSpecies => stats.species.len() >= FishVariant::iterator().len(),
+            Condition::FishWeight(weight) => stats.heaviest_fish >= weight,
+            Condition::PortWeight(weight) => stats.port_weight >= weight,
+            Condition::Haul(weight) => stats.largest_haul >= weight,
+            Condition::Upgrades(count) => stats.upgrades >= count,
+            Condition::TrashHits(count) => stats.trash_hits >= count,
+        }
+    }
+}
+
+/// One achievement, loaded from `assets/data/achievements.ron`.
+#[derive(Deserialize, Debug, Clone)]
+pub struct AchievementSpec {
+    /// Kept in the player's unlocks, so it mustn't change once released.
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub condition: Condition,
+}
+
+#[derive(Resource, Deserialize, Debug)]
+pub struct AchievementCatalog {
+    pub achievements: Vec<AchievementSpec>,
+}
+
+impl AchievementCatalog {
+    pub const PATH: &'static str = "data/achievements.ron";
+
+    pub fn load() -> Self {
+        load_data_file(Self::PATH)
+    }
+}
+
+/// Everything achievements are judged on.
+#[derive(Serialize, Deserialize, Default, Debug)]
+pub struct AchievementStats {
+    pub catches: u32,
+    pub trophies: u32,
+    pub species: HashSet<FishVariant>,
+    /// In kg.
+    pub heaviest_fish: f32,
+    /// Most kg sold at the port over one game.
+    pub port_weight: f32,
+    /// Most kg sold at the port in one go.
+    pub largest_haul: f32,
+    pub upgrades: u32,
+    pub trash_hits: u32,
+}
+
+/// The player's achievements over every game they've played, kept in their
+/// own file rather than with the save.
+#[derive(Resource, Serialize, Deserialize, Default, Debug)]
+pub struct Achievements {
+    pub stats: AchievementStats,
+    /// Ids of the achievements unlocked.
+    pub unlocked: HashSet<String>,
+}
+
+impl Achievements {
+    const FILE: &'static str = "achievements.ron";
+
+    /// The saved achievements, or none if nothing has been unlocked yet.
+    pub fn load() -> Self {
+        let Some(path) = data_path(Self::FILE).filter(|path| path.exists()) else {
+            return Self::default();
+        };
+
+        fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|contents| ron::from_str(&contents).map_err(|e| e.to_string()))
+            .unwrap_or_else(|e| {
+                error!("failed to load achievements {}: {e}", path.display());
+                Self::default()
+            })
+    }
+
+    pub fn write(&self) {
+        write_data_file(Self::FILE, self);
+    }
+
+    pub fn is_unlocked(&self, spec: &AchievementSpec) -> bool {
+        self.unlocked.contains(&spec.id)
+    }
+}
+
+/// A banner announcing an unlock, which goes away by itself.
+#[derive(Component)]
+struct Toast(Timer);
+
+#[derive(Component)]
+struct OnAchievementsScreen;
+
+#[derive(Component)]
+struct AchievementsBackButton;
+
+pub struct AchievementsPlugin;
+
+impl Plugin for AchievementsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(AchievementCatalog::load())
+            .insert_resource(Achievements::load())
+            .add_event::<AchievementUnlockedEvent>()
+            .add_systems(
+                Update,
+                (
+                    (track_stats, unlock_achievements, show_toasts)
+                        .chain()
+                        .after(GameplaySet::Shop)
+                        .before(GameplaySet::Speech)
+                        .run_if(in_state(Game)),
+                    write_achievements.run_if(resource_changed::<Achievements>()),
+                )
+                    // Replays shouldn't count towards the player's real
+                    // achievements
+                    .run_if(not(resource_exists::<Replay>())),
+            )
+            .add_systems(Update, expire_toasts)
+            .add_systems(OnEnter(MenuState::Achievements), achievements_setup)
+            .add_systems(
+                OnExit(MenuState::Achievements),
+                despawn_screen::<OnAchievementsScreen>,
+            )
+            .add_systems(
+                Update,
+                achievements_action.run_if(in_state(MenuState::Achievements)),
+            );
+    }
+}
+
+fn track_stats(
+    mut ev_catch_fish: EventReader<CatchFishEvent>,
+    mut ev_deposit: EventReader<DepositFishEvent>,
+    mut ev_upgrade: EventReader<UpgradeEvent>,
+    mut ev_trash_collision: EventReader<TrashCollisionEvent>,
+    mut achievements: ResMut<Achievements>,
+) {
+    if ev_catch_fish.is_empty()
+        && ev_deposit.is_empty()
+        && ev_upgrade.is_empty()
+        && ev_trash_collision.is_empty()
+    {
+        return;
+    }
+    let stats = &mut achievements.stats;
+
+    for ev in ev_catch_fish.read() {
+        stats.catches += 1;
+        stats.trophies += ev.trophy as u32;
+        stats.species.insert(ev.fish_variant);
+        stats.heaviest_fish = stats.heaviest_fish.max(ev.weight.current);
+    }
+
+    for ev in ev_deposit.read() {
+        stats.port_weight = stats.port_weight.max(ev.port_weight);
+        stats.largest_haul = stats.largest_haul.max(ev.weight);
+    }
+
+    stats.upgrades += ev_upgrade.read().count() as u32;
+    stats.trash_hits += ev_trash_collision.read().count() as u32;
+}
+
+fn unlock_achievements(
+    catalog: Res<AchievementCatalog>,
+    mut achievements: ResMut<Achievements>,
+    mut ev_unlocked: EventWriter<AchievementUnlockedEvent>,
+) {
+    if !achievements.is_changed() {
+        return;
+    }
+
+    for (index, spec) in catalog.achievements.iter().enumerate() {
+        if !achievements.is_unlocked(spec) && spec.condition.is_met(&achievements.stats) {
+            achievements.unlocked.insert(spec.id.clone());
+            ev_unlocked.send(AchievementUnlockedEvent { index });
+        }
+    }
+}
+
+fn show_toasts(
+    mut commands: Commands,
+    mut ev_unlocked: EventReader<AchievementUnlockedEvent>,
+    asset_server: Res<AssetServer>,
+    catalog: Res<AchievementCatalog>,
+    toast_query: Query<(), With<Toast>>,
+) {
+    // Stack under any still showing
+    let showing = toast_query.iter().count();
+
+    for (showing, ev) in (showing..).zip(ev_unlocked.read()) {
+        let spec = &catalog.achievements[ev.index];
+
+        commands
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        top: Val::Px(20. + showing as f32 * (TOAST_HEIGHT + 10.)),
+                        left: Val::Percent(50.),
+                        margin: UiRect::left(Val::Px(-200.)),
+                        width: Val::Px(400.),
+                        height: Val::Px(TOAST_HEIGHT),
+                        flex_direction: FlexDirection::Column,
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    background_color: TOAST_COLOR.into(),
+                    z_index: ZIndex::Global(10),
+                    ..default()
+                },
+                Toast(Timer::from_seconds(TOAST_TIME, TimerMode::Once)),
+            ))
+            .with_children(|parent| {
+                parent.spawn(TextBundle::from_section(
+                    format!("Achievement unlocked: {}", spec.name),
+                    TextStyle {
+                        font: asset_server.load("fonts/Pixellari.ttf"),
+                        font_size: 22.0,
+                        color: TEXT_COLOR,
+                    },
+                ));
+                parent.spawn(TextBundle::from_section(
+                    spec.description.clone(),
+                    TextStyle {
+                        font: asset_server.load("fonts/Pixellari.ttf"),
+                        font_size: 16.0,
+                        color: TEXT_COLOR,
+                    },
+                ));
+            });
+    }
+}
+
+/// Toasts count down in real time, so they still go away with the game
+/// paused.
+fn expire_toasts(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut toast_query: Query<(Entity, &mut Toast)>,
+) {
+    for (toast, mut timer) in &mut toast_query {
+        if timer.0.tick(time.delta()).finished() {
+            commands.entity(toast).despawn_recursive();
+        }
+    }
+}
+
+fn write_achievements(achievements: Res<Achievements>) {
+    achievements.write();
+}
+
+fn achievements_setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    catalog: Res<AchievementCatalog>,
+    achievements: Res<Achievements>,
+) {
+    let text_style = TextStyle {
+        font: asset_server.load("fonts/Pixellari.ttf"),
+        font_size: 22.0,
+        color: TEXT_COLOR,
+    };
+    let detail_style = TextStyle {
+        font_size: 16.0,
+        ..text_style.clone()
+    };
+    let unlocked = catalog
+        .achievements
+        .iter()
+        .filter(|spec| achievements.is_unlocked(spec))
+        .count();
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.85).into(),
+                ..default()
+            },
+            OnAchievementsScreen,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Achievements",
+                TextStyle {
+                    font_size: 50.0,
+                    ..text_style.clone()
+                },
+            ));
+            parent.spawn(
+                TextBundle::from_section(
+                    format!("{unlocked} of {} unlocked", catalog.achievements.len()),
+                    detail_style.clone(),
+                )
+                .with_style(Style {
+                    margin: UiRect::bottom(Val::Px(10.0)),
+                    ..default()
+                }),
+            );
+
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Px(1000.0),
+                        flex_wrap: FlexWrap::Wrap,
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for spec in &catalog.achievements {
+                        let color = if achievements.is_unlocked(spec) {
+                            TEXT_COLOR
+                        } else {
+                            LOCKED_TEXT_COLOR
+                        };
+
+                        parent
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    width: Val::Px(480.0),
+                                    height: Val::Px(60.0),
+                                    margin: UiRect::all(Val::Px(5.0)),
+                                    padding: UiRect::horizontal(Val::Px(10.0)),
+                                    flex_direction: FlexDirection::Column,
+                                    justify_content: JustifyContent::Center,
+                                    ..default()
+                                },
+                                background_color: Color::rgba(1., 1., 1., 0.06).into(),
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section(
+                                    spec.name.clone(),
+                                    TextStyle {
+                                        color,
+                                        ..text_style.clone()
+                                    },
+                                ));
+                                parent.spawn(TextBundle::from_section(
+                                    spec.description.clone(),
+                                    TextStyle {
+                                        color,
+                                        ..detail_style.clone()
+                                    },
+                                ));
+                            });
+                    }
+                });
+
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: Style {
+                            width: Val::Px(200.0),
+                            height: Val::Px(50.0),
+                            margin: UiRect::all(Val::Px(10.0)),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        background_color: NORMAL_BUTTON.into(),
+                        ..default()
+                    },
+                    AchievementsBackButton,
+                ))
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section("Back", text_style));
+                });
+        });
+}
+
+/// Achievements are reached from the pause menu as well as the main menu.
+fn achievements_action(
+    back_query: Query<&Interaction, (Changed<Interaction>, With<AchievementsBackButton>)>,
+    pause_state: Res<State<PauseState>>,
+    mut menu_state: ResMut<NextState<MenuState>>,
+) {
+    for interaction in &back_query {
+        if *interaction == Interaction::Pressed {
+            match pause_state.get() {
+                PauseState::Paused => menu_state.set(MenuState::Pause),
+                PauseState::Running => menu_state.set(MenuState::Main),
+            }
+        }
+    }
+}
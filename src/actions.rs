@@ -0,0 +1,271 @@
+use bevy::{input::InputSystem, prelude::*};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs};
+
+use crate::{
+    replay::Replay,
+    save::{data_path, write_data_file},
+};
+
+/// What the player asked for this frame, independent of where the input came
+/// from. Gameplay reads `Input<GameAction>` rather than the keyboard, so a
+/// replay or a bot can drive it instead.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Hash, Serialize, Deserialize)]
+pub enum GameAction {
+    MoveLeft,
+    MoveRight,
+    Cast,
+    Reel,
+    SwitchBait,
+    Map,
+    Pause,
+}
+
+impl GameAction {
+    pub const ALL: [GameAction; 7] = [
+        GameAction::MoveLeft,
+        GameAction::MoveRight,
+        GameAction::Cast,
+        GameAction::Reel,
+        GameAction::SwitchBait,
+        GameAction::Map,
+        GameAction::Pause,
+    ];
+
+    pub fn description(self) -> &'static str {
+        match self {
+            GameAction::MoveLeft => "Move boat left",
+            GameAction::MoveRight => "Move boat right",
+            GameAction::Cast => "Cast rod",
+            GameAction::Reel => "Jerk rod upwards",
+            GameAction::SwitchBait => "Switch bait",
+            GameAction::Map => "Open the sea chart at the port",
+            GameAction::Pause => "Pause the game",
+        }
+    }
+}
+
+/// How far the left stick has to be pushed to move the boat.
+const STICK_THRESHOLD: f32 = 0.5;
+
+/// The key and gamepad button for each action, as chosen on the Controls
+/// settings page.
+#[derive(Resource, Clone, Debug, Serialize, Deserialize)]
+pub struct Bindings {
+    keys: HashMap<GameAction, KeyCode>,
+    buttons: HashMap<GameAction, GamepadButtonType>,
+}
+
+impl Default for Bindings {
+    fn default() -> Self {
+        Self {
+            keys: HashMap::from([
+                (GameAction::MoveLeft, KeyCode::Left),
+                (GameAction::MoveRight, KeyCode::Right),
+                (GameAction::Cast, KeyCode::Down),
+                (GameAction::Reel, KeyCode::Space),
+                (GameAction::SwitchBait, KeyCode::B),
+                (GameAction::Map, KeyCode::M),
+                (GameAction::Pause, KeyCode::Escape),
+            ]),
+            buttons: HashMap::from([
+                (GameAction::MoveLeft, GamepadButtonType::DPadLeft),
+                (GameAction::MoveRight, GamepadButtonType::DPadRight),
+                (GameAction::Cast, GamepadButtonType::DPadDown),
+                (GameAction::Reel, GamepadButtonType::South),
+                (GameAction::SwitchBait, GamepadButtonType::North),
+                (GameAction::Map, GamepadButtonType::Select),
+                (GameAction::Pause, GamepadButtonType::Start),
+            ]),
+        }
+    }
+}
+
+impl Bindings {
+    const FILE: &'static str = "controls.ron";
+
+    /// The saved bindings, or the defaults if none have been saved yet.
+    pub fn load() -> Self {
+        let Some(path) = data_path(Self::FILE).filter(|path| path.exists()) else {
+            return Self::default();
+        };
+
+        let mut bindings: Self = fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|contents| ron::from_str(&contents).map_err(|e| e.to_string()))
+            .unwrap_or_else(|e| {
+                error!("failed to load controls {}: {e}", path.display());
+                Self::default()
+            });
+        bindings.bind_missing();
+        bindings
+    }
+
+    /// Give actions added since the bindings were saved their default, unless
+    /// something else has been bound to it in the meantime.
+    fn bind_missing(&mut self) {
+        let defaults = Self::default();
+
+        for action in GameAction::ALL {
+            if let Some(key) = defaults.key(action) {
+                if !self.keys.values().any(|bound| *bound == key) {
+                    self.keys.entry(action).or_insert(key);
+                }
+            }
+            if let Some(button) = defaults.button(action) {
+                if !self.buttons.values().any(|bound| *bound == button) {
+                    self.buttons.entry(action).or_insert(button);
+                }
+            }
+        }
+    }
+
+    pub fn write(&self) {
+        write_data_file(Self::FILE, self);
+    }
+
+    pub fn key(&self, action: GameAction) -> Option<KeyCode> {
+        self.keys.get(&action).copied()
+    }
+
+    pub fn button(&self, action: GameAction) -> Option<GamepadButtonType> {
+        self.buttons.get(&action).copied()
+    }
+
+    /// Bind `key` to `action`. An action already using the key swaps over to
+    /// the one `action` had, so nothing is ever left sharing a key.
+    pub fn bind_key(&mut self, action: GameAction, key: KeyCode) {
+        swap_binding(&mut self.keys, action, key);
+    }
+
+    /// Bind `button` to `action`, swapping like [`Bindings::bind_key`].
+    pub fn bind_button(&mut self, action: GameAction, button: GamepadButtonType) {
+        swap_binding(&mut self.buttons, action, button);
+    }
+
+    pub fn key_name(&self, action: GameAction) -> String {
+        match self.key(action) {
+            Some(KeyCode::Left) => "Left Arrow".into(),
+            Some(KeyCode::Right) => "Right Arrow".into(),
+            Some(KeyCode::Up) => "Up Arrow".into(),
+            Some(KeyCode::Down) => "Down Arrow".into(),
+            Some(key) => format!("{key:?}"),
+            None => "-".into(),
+        }
+    }
+
+    pub fn button_name(&self, action: GameAction) -> String {
+        match self.button(action) {
+            Some(GamepadButtonType::South) => "A".into(),
+            Some(GamepadButtonType::East) => "B".into(),
+            Some(GamepadButtonType::West) => "X".into(),
+            Some(GamepadButtonType::North) => "Y".into(),
+            Some(GamepadButtonType::DPadLeft) => "D-Pad Left".into(),
+            Some(GamepadButtonType::DPadRight) => "D-Pad Right".into(),
+            Some(GamepadButtonType::DPadUp) => "D-Pad Up".into(),
+            Some(GamepadButtonType::DPadDown) => "D-Pad Down".into(),
+            Some(button) => format!("{button:?}"),
+            None => "-".into(),
+        }
+    }
+
+    /// One line per action, for the controls shown during the game.
+    pub fn describe(&self) -> String {
+        GameAction::ALL
+            .into_iter()
+            .map(|action| {
+                format!(
+                    "{} / {} - {}",
+                    self.key_name(action),
+                    self.button_name(action),
+                    action.description()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+fn swap_binding<T: Copy + PartialEq>(
+    bindings: &mut HashMap<GameAction, T>,
+    action: GameAction,
+    input: T,
+) {
+    let previous = bindings.insert(action, input);
+    let clashing = bindings
+        .iter()
+        .find(|(other, bound)| **other != action && **bound == input)
+        .map(|(other, _)| *other);
+
+    if let Some(other) = clashing {
+        match previous {
+            Some(previous) => bindings.insert(other, previous),
+            None => bindings.remove(&other),
+        };
+    }
+}
+
+/// Runs once `Input<GameAction>` holds this frame's actions.
+#[derive(SystemSet, Clone, Copy, Eq, PartialEq, Debug, Hash)]
+pub struct ActionSystem;
+
+pub struct ActionsPlugin;
+
+impl Plugin for ActionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Input<GameAction>>()
+            .insert_resource(Bindings::load())
+            .add_systems(
+                PreUpdate,
+                read_input
+                    .in_set(ActionSystem)
+                    .after(InputSystem)
+                    .run_if(not(resource_exists::<Replay>())),
+            );
+    }
+}
+
+/// Hold down exactly the given actions, releasing the rest.
+pub fn set_actions(actions: &mut Input<GameAction>, pressed: impl Fn(GameAction) -> bool) {
+    actions.clear();
+
+    for action in GameAction::ALL {
+        if pressed(action) {
+            actions.press(action);
+        } else {
+            actions.release(action);
+        }
+    }
+}
+
+fn read_input(
+    bindings: Res<Bindings>,
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_input: Res<Input<GamepadButton>>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    mut actions: ResMut<Input<GameAction>>,
+) {
+    set_actions(&mut actions, |action| {
+        let key = bindings
+            .key(action)
+            .is_some_and(|key| keyboard_input.pressed(key));
+
+        let gamepad = gamepads.iter().any(|gamepad| {
+            let button = bindings
+                .button(action)
+                .is_some_and(|button| gamepad_input.pressed(GamepadButton::new(gamepad, button)));
+            let stick = gamepad_axes
+                .get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickX))
+                .is_some_and(|x| match action {
+                    GameAction::MoveLeft => x < -STICK_THRESHOLD,
+                    GameAction::MoveRight => x > STICK_THRESHOLD,
+                    _ => false,
+                });
+
+            button || stick
+        });
+
+        key || gamepad
+    });
+}
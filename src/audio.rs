@@ -1,19 +1,27 @@
 use crate::components::BGMPlayer;
 use crate::events::{
-    CatchFishEvent, DepositFishEvent, DropFishEvent, ReelingFishEvent, TrashCollisionEvent,
+    CatchFishEvent, DepositFishEvent, DropFishEvent, LineSnapEvent, ReelingFishEvent,
+    TrashCollisionEvent,
 };
-use crate::GameState::Game;
-use bevy::audio::{PlaybackMode, Volume as BevyVolume};
+use crate::location::Location;
+use crate::resources::Volume;
+use crate::weather::{Weather, DOWNPOUR};
+use crate::GameState::{self, Game};
+use bevy::audio::{AddAudioSource, Decodable, PlaybackMode, Source, Volume as BevyVolume};
 use bevy::prelude::*;
+use std::time::Duration;
 
-pub struct AudioPlugin;
+const RAIN_SAMPLE_RATE: u32 = 44_100;
+/// How much of each new sample of noise goes into the rain, lower being a
+/// softer hiss.
+const RAIN_SMOOTHING: f32 = 0.3;
 
-#[derive(Resource, Debug, Component, PartialEq, Clone, Copy)]
-pub struct Volume(pub(crate) f32);
+pub struct AudioPlugin;
 
 impl Plugin for AudioPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, setup)
+        app.add_audio_source::<RainNoise>()
+            .add_systems(Startup, setup)
             .add_systems(
                 Update,
                 (
@@ -22,17 +30,114 @@ impl Plugin for AudioPlugin {
                     check_for_drop_fish_events,
                     check_for_trash_collision_events,
                     check_for_reeling_events,
+                    check_for_line_snap_events,
                 )
                     .run_if(in_state(Game)),
             )
-            .add_systems(Update, check_for_volume_events);
+            .add_systems(
+                Update,
+                play_location_music.run_if(resource_exists_and_changed::<Location>()),
+            )
+            .add_systems(Update, (check_for_volume_events, update_rain_volume));
+    }
+}
+
+/// Rain ambience. Generated rather than recorded, as smoothed white noise
+/// that never runs out.
+#[derive(Asset, TypePath)]
+struct RainNoise;
+
+impl Decodable for RainNoise {
+    type DecoderItem = f32;
+    type Decoder = RainNoiseDecoder;
+
+    fn decoder(&self) -> Self::Decoder {
+        RainNoiseDecoder {
+            state: 0x2545_f491,
+            last: 0.,
+        }
+    }
+}
+
+struct RainNoiseDecoder {
+    /// Xorshift state. The noise doesn't need to be any good, or to come
+    /// from the `GameRng`.
+    state: u32,
+    last: f32,
+}
+
+impl Iterator for RainNoiseDecoder {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 17;
+        self.state ^= self.state << 5;
+
+        let white = self.state as f32 / u32::MAX as f32 * 2. - 1.;
+        self.last += (white - self.last) * RAIN_SMOOTHING;
+        Some(self.last)
+    }
+}
+
+impl Source for RainNoiseDecoder {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        RAIN_SAMPLE_RATE
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
     }
 }
 
-fn setup(volume: Res<Volume>, mut commands: Commands, asset_server: Res<AssetServer>) {
+#[derive(Component)]
+struct RainPlayer;
+
+fn setup(mut commands: Commands, mut rain_noise: ResMut<Assets<RainNoise>>) {
+    // Never ends, so play it once rather than looping, which would buffer
+    // it all to start over
+    commands.spawn((
+        AudioSourceBundle {
+            source: rain_noise.add(RainNoise),
+            settings: PlaybackSettings {
+                mode: PlaybackMode::Once,
+                volume: BevyVolume::new_absolute(0.),
+                ..default()
+            },
+        },
+        RainPlayer,
+    ));
+}
+
+/// Put on the music of wherever the session has moved to, leaving it playing
+/// if it's the same tune.
+fn play_location_music(
+    mut commands: Commands,
+    volume: Res<Volume>,
+    location: Res<Location>,
+    asset_server: Res<AssetServer>,
+    music_query: Query<(Entity, &Handle<AudioSource>), With<BGMPlayer>>,
+) {
+    let music = asset_server.load(&location.0.music);
+
+    if let Ok((player, playing)) = music_query.get_single() {
+        if *playing == music {
+            return;
+        }
+        commands.entity(player).despawn();
+    }
+
     commands.spawn((
         AudioBundle {
-            source: asset_server.load("audio/bg-1.ogg"),
+            source: music,
             settings: PlaybackSettings {
                 mode: PlaybackMode::Loop,
                 volume: BevyVolume::new_absolute(volume.0),
@@ -78,6 +183,24 @@ fn check_for_volume_events(
     }
 }
 
+/// Louder the harder it rains, and silent away from the water.
+fn update_rain_volume(
+    volume: Res<Volume>,
+    weather: Res<Weather>,
+    game_state: Res<State<GameState>>,
+    rain_player: Query<&AudioSink, With<RainPlayer>>,
+) {
+    let Ok(sink) = rain_player.get_single() else {
+        return;
+    };
+
+    let intensity = match game_state.get() {
+        GameState::Game => (weather.rain / DOWNPOUR).clamp(0., 1.),
+        _ => 0.,
+    };
+    sink.set_volume(volume.0 * intensity);
+}
+
 fn check_for_catch_fish_events(
     commands: Commands,
     world: &World,
@@ -117,3 +240,11 @@ fn check_for_reeling_events(
 ) {
     handle_audio_events(commands, world, ev_reeling_fish, "audio/pop-1.ogg");
 }
+
+fn check_for_line_snap_events(
+    commands: Commands,
+    world: &World,
+    ev_line_snap: EventReader<LineSnapEvent>,
+) {
+    handle_audio_events(commands, world, ev_line_snap, "audio/drop-1.ogg");
+}
@@ -0,0 +1,231 @@
+use crate::{
+    actions::GameAction,
+    events::{DepositFishEvent, ReelingFishEvent},
+    fish::FishVariant,
+    resources::load_data_file,
+    rod::Rod,
+    GameState::Game,
+    GameplaySet,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Chance a fish bites bait it has no preference for.
+const UNPREFERRED_BITE_CHANCE: f32 = 0.2;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum BaitKind {
+    Worm,
+    Shrimp,
+    Spinner,
+    Jig,
+}
+
+/// Tuning for one kind of bait, loaded from `assets/data/bait.ron`.
+#[derive(Deserialize, Debug)]
+pub struct Bait {
+    pub kind: BaitKind,
+    pub name: String,
+    /// Coins for a pack of `pack_size`.
+    pub cost: u32,
+    pub pack_size: u32,
+    /// How many the port hands out free with every sale.
+    #[serde(default)]
+    pub earned_per_deposit: u32,
+    /// How far away fish notice the bait, in px.
+    pub attraction_radius: f32,
+    /// Chance each species bites. Listed species also swim over to the hook.
+    pub preferences: HashMap<FishVariant, f32>,
+}
+
+/// Every bait the port sells.
+#[derive(Resource, Deserialize, Debug)]
+pub struct BaitCatalog {
+    pub baits: Vec<Bait>,
+}
+
+impl BaitCatalog {
+    pub const PATH: &'static str = "data/bait.ron";
+
+    pub fn load() -> Self {
+        load_data_file(Self::PATH)
+    }
+
+    pub fn get(&self, kind: BaitKind) -> &Bait {
+        self.baits
+            .iter()
+            .find(|bait| bait.kind == kind)
+            .expect("every bait kind to have a catalog entry")
+    }
+}
+
+/// The bait the player is carrying, and which of it goes on the hook.
+#[derive(Resource, Default, Debug, Clone)]
+pub struct BaitInventory {
+    pub counts: HashMap<BaitKind, u32>,
+    /// `None` casts a bare hook.
+    pub selected: Option<BaitKind>,
+}
+
+impl BaitInventory {
+    pub fn count(&self, kind: BaitKind) -> u32 {
+        self.counts.get(&kind).copied().unwrap_or(0)
+    }
+
+    /// Add `amount` of `kind`, putting it on the hook if nothing else is.
+    pub fn add(&mut self, kind: BaitKind, amount: u32) {
+        *self.counts.entry(kind).or_default() += amount;
+        self.selected.get_or_insert(kind);
+    }
+
+    /// Use up one of `kind`, returning whether there was one to use.
+    pub fn take(&mut self, kind: BaitKind) -> bool {
+        match self.counts.get_mut(&kind) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// The selected bait, if there's any of it left.
+    pub fn ready(&self) -> Option<BaitKind> {
+        self.selected.filter(|kind| self.count(*kind) > 0)
+    }
+
+    /// Select the next bait the player has any of, in catalog order, going
+    /// round through the bare hook.
+    pub fn cycle(&mut self, catalog: &BaitCatalog) {
+        let options: Vec<Option<BaitKind>> = std::iter::once(None)
+            .chain(
+                catalog
+                    .baits
+                    .iter()
+                    .filter(|bait| self.count(bait.kind) > 0)
+                    .map(|bait| Some(bait.kind)),
+            )
+            .collect();
+
+        let current = options
+            .iter()
+            .position(|option| *option == self.ready())
+            .unwrap_or(0);
+        self.selected = options[(current + 1) % options.len()];
+    }
+}
+
+/// Bait on the hook. Fish it appeals to steer over to it.
+#[derive(Component, Debug)]
+pub struct Lure {
+    pub kind: BaitKind,
+    pub radius: f32,
+    preferences: HashMap<FishVariant, f32>,
+}
+
+impl Lure {
+    pub fn new(bait: &Bait) -> Self {
+        Self {
+            kind: bait.kind,
+            radius: bait.attraction_radius,
+            preferences: bait.preferences.clone(),
+        }
+    }
+
+    /// How strongly `variant` is drawn to the hook, from 0 to 1.
+    pub fn appeal(&self, variant: FishVariant) -> f32 {
+        self.preferences.get(&variant).copied().unwrap_or(0.)
+    }
+
+    /// Chance `variant` bites the hook. A bare hook has nothing to put fish
+    /// off, so it's left catching whatever swims into it, but bait meant for
+    /// other species only gets the odd bite.
+    pub fn bite_chance(&self, variant: FishVariant) -> f32 {
+        self.preferences
+            .get(&variant)
+            .copied()
+            .unwrap_or(UNPREFERRED_BITE_CHANCE)
+    }
+
+    /// Whether `variant` eats the bait when it bites, rather than just
+    /// taking the hook.
+    pub fn is_eaten_by(&self, variant: FishVariant) -> bool {
+        self.preferences.contains_key(&variant)
+    }
+}
+
+pub struct BaitPlugin;
+
+impl Plugin for BaitPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(BaitCatalog::load())
+            .init_resource::<BaitInventory>()
+            .add_systems(
+                Update,
+                (switch_bait, bait_hook, use_bait, earn_bait)
+                    .chain()
+                    .in_set(GameplaySet::Bait)
+                    .run_if(in_state(Game)),
+            );
+    }
+}
+
+fn switch_bait(
+    actions: Res<Input<GameAction>>,
+    catalog: Res<BaitCatalog>,
+    mut inventory: ResMut<BaitInventory>,
+) {
+    if actions.just_pressed(GameAction::SwitchBait) {
+        inventory.cycle(&catalog);
+    }
+}
+
+/// Put the selected bait on a freshly cast hook.
+fn bait_hook(
+    mut commands: Commands,
+    hook_query: Query<Entity, Added<Rod>>,
+    catalog: Res<BaitCatalog>,
+    inventory: Res<BaitInventory>,
+) {
+    let Some(kind) = inventory.ready() else {
+        return;
+    };
+
+    for hook in &hook_query {
+        commands.entity(hook).insert(Lure::new(catalog.get(kind)));
+    }
+}
+
+/// A fish that bites for the bait eats it.
+fn use_bait(
+    mut commands: Commands,
+    mut ev_reeling_fish: EventReader<ReelingFishEvent>,
+    hook_query: Query<(Entity, &Lure), With<Rod>>,
+    mut inventory: ResMut<BaitInventory>,
+) {
+    for ev in ev_reeling_fish.read() {
+        let Ok((hook, lure)) = hook_query.get_single() else {
+            continue;
+        };
+
+        if lure.is_eaten_by(ev.fish_variant) {
+            inventory.take(lure.kind);
+            commands.entity(hook).remove::<Lure>();
+        }
+    }
+}
+
+fn earn_bait(
+    mut ev_deposit: EventReader<DepositFishEvent>,
+    catalog: Res<BaitCatalog>,
+    mut inventory: ResMut<BaitInventory>,
+) {
+    for _ in ev_deposit.read() {
+        for bait in &catalog.baits {
+            if bait.earned_per_deposit > 0 {
+                inventory.add(bait.kind, bait.earned_per_deposit);
+            }
+        }
+    }
+}
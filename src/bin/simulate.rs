@@ -0,0 +1,478 @@
+//! Plays the game headlessly with a scripted bot and reports balance
+//! statistics: how long each rod takes to unlock, what gets caught, and
+//! how much is lost along the way.
+//!
+//! ```sh
+//! cargo run --release --bin simulate -- --minutes 60 --seed 42
+//! ```
+//!
+//! The seed is printed at the start of every run, and passing it back in
+//! plays the exact same run again.
+
+use bevy::{
+    app::AppExit,
+    asset::io::{AssetReader, AssetReaderError, AssetSource, AssetSourceId, PathStream, Reader},
+    input::InputPlugin,
+    prelude::*,
+    time::TimeUpdateStrategy,
+    utils::{BoxedFuture, HashMap},
+};
+use gone_fish::{
+    actions::{ActionSystem, ActionsPlugin, GameAction},
+    bait::BaitInventory,
+    camera::CameraPlugin,
+    clock::ClockPlugin,
+    components::FishStorage,
+    configure_gameplay_sets,
+    contracts::ContractsPlugin,
+    events::{
+        CatchFishEvent, ContractCompletedEvent, DepositFishEvent, DropFishEvent, LineSnapEvent,
+        PortCollisionEvent, TrashCollisionEvent, UpgradeEvent,
+    },
+    fish::{Fish, FishPlugin, FishState, FishVariant},
+    journal::FishJournal,
+    location::LocationPlugin,
+    menu::MenuState,
+    player::{BoatSpeed, Player, PlayerPlugin},
+    port::PortPlugin,
+    resources::{GameRng, PortStorage},
+    rod::{LineTension, Rod, RodPlugin, RodVariant},
+    shop::ShopCatalog,
+    spoilage::{IceBox, SpoilagePlugin},
+    systems::tick_decay_timers,
+    trash::TrashPlugin,
+    weather::WeatherPlugin,
+    world::{WorldMap, WorldPlugin},
+    GameState::{self, Game},
+    GameplaySet,
+};
+use rand::Rng;
+use std::{path::Path, time::Duration};
+
+/// Simulated frame length. Fixed so runs don't depend on how fast the
+/// machine is.
+const TIMESTEP: f32 = 1. / 60.;
+const DEFAULT_MINUTES: f32 = 60.;
+
+/// Nearest to port the bot rows out to before casting. It goes as far out
+/// as the boat can make it.
+const NEAREST_SPOT: f32 = -300.;
+/// How far either way the bot moves on to try its luck somewhere else.
+const SPOT_SPREAD: f32 = 600.;
+/// How far short of the swell the boat can't get through the bot stops.
+const SWELL_MARGIN: f32 = 100.;
+/// Head back to port once the hold is this full.
+const RETURN_AT_CAPACITY: f32 = 0.75;
+/// How long to leave an empty hook at the bottom before reeling it in.
+const SOAK_TIME: f32 = 3.;
+const REEL_INTERVAL: f32 = 0.15;
+/// Stop reeling a fighting fish while the line is this tense.
+const SAFE_TENSION: f32 = 0.5;
+
+fn main() {
+    let (minutes, seed) = parse_args();
+    let rng = GameRng::from_seed_or_entropy(seed);
+    println!("Seed {}", rng.seed());
+
+    let mut app = App::new();
+    // Gameplay hands out handles to sprites and fonts, but none of them are
+    // ever read from disk
+    app.register_asset_source(
+        AssetSourceId::Default,
+        AssetSource::build().with_reader(|| Box::new(NoAssets)),
+    )
+    .add_plugins((MinimalPlugins, AssetPlugin::default(), InputPlugin))
+    .init_asset::<Image>()
+    .init_asset::<TextureAtlas>()
+    .init_asset::<Font>()
+    .init_asset::<AnimationClip>()
+    .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+        TIMESTEP,
+    )))
+    .insert_resource(rng)
+    .insert_resource(ShopCatalog::load())
+    .insert_resource(Simulation {
+        duration: minutes * 60.,
+        ..default()
+    })
+    .init_resource::<Bot>()
+    // The bot never buys bait or reads the journal, but travelling
+    // packs them along
+    .init_resource::<BaitInventory>()
+    .init_resource::<FishJournal>()
+    .add_state::<GameState>()
+    .add_state::<MenuState>()
+    .add_plugins((
+        ActionsPlugin,
+        LocationPlugin,
+        CameraPlugin,
+        ClockPlugin,
+        WeatherPlugin,
+        WorldPlugin,
+        PlayerPlugin,
+        RodPlugin,
+        FishPlugin,
+        PortPlugin,
+        ContractsPlugin,
+        SpoilagePlugin,
+        TrashPlugin,
+    ))
+    .add_systems(Startup, start_game)
+    .add_systems(PreUpdate, bot.after(ActionSystem).run_if(in_state(Game)))
+    .add_systems(
+        Update,
+        (
+            tick_decay_timers.before(GameplaySet::Player),
+            record_rods,
+            record_events,
+            finish.after(record_events),
+        )
+            .run_if(in_state(Game)),
+    );
+
+    configure_gameplay_sets(&mut app);
+
+    app.run();
+}
+
+/// An asset source with nothing in it, so every load fails straight away
+/// without touching the `assets` folder.
+struct NoAssets;
+
+impl AssetReader for NoAssets {
+    fn read<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<Reader<'a>>, AssetReaderError>> {
+        Box::pin(async move { Err(AssetReaderError::NotFound(path.to_owned())) })
+    }
+
+    fn read_meta<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<Reader<'a>>, AssetReaderError>> {
+        Box::pin(async move { Err(AssetReaderError::NotFound(path.to_owned())) })
+    }
+
+    fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<PathStream>, AssetReaderError>> {
+        Box::pin(async move { Err(AssetReaderError::NotFound(path.to_owned())) })
+    }
+
+    fn is_directory<'a>(
+        &'a self,
+        _path: &'a Path,
+    ) -> BoxedFuture<'a, Result<bool, AssetReaderError>> {
+        Box::pin(async move { Ok(false) })
+    }
+}
+
+const USAGE: &str = "usage: simulate [--minutes <number>] [--seed <number>]";
+
+fn parse_args() -> (f32, Option<u64>) {
+    let mut args = std::env::args().skip(1);
+    let mut minutes = DEFAULT_MINUTES;
+    let mut seed = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--minutes" => {
+                minutes = args
+                    .next()
+                    .and_then(|value| value.parse().ok())
+                    .unwrap_or_else(|| usage_error("--minutes needs a number"));
+            }
+            "--seed" => {
+                seed = Some(
+                    args.next()
+                        .and_then(|value| value.parse().ok())
+                        .unwrap_or_else(|| usage_error("--seed needs a number")),
+                );
+            }
+            _ => usage_error(&format!("unknown argument {arg}")),
+        }
+    }
+
+    (minutes, seed)
+}
+
+fn usage_error(message: &str) -> ! {
+    eprintln!("{message}\n{USAGE}");
+    std::process::exit(2);
+}
+
+fn start_game(mut game_state: ResMut<NextState<GameState>>) {
+    game_state.set(Game);
+}
+
+#[derive(Default)]
+struct CatchStats {
+    count: u32,
+    total_weight: f32,
+}
+
+#[derive(Resource, Default)]
+struct Simulation {
+    duration: f32,
+    rods: Vec<(RodVariant, f32)>,
+    catches: HashMap<FishVariant, CatchStats>,
+    trash_drops: u32,
+    line_snaps: u32,
+    hold_full_drops: u32,
+    deposits: u32,
+    coins_earned: u32,
+    contracts: u32,
+    contract_coins: u32,
+}
+
+#[derive(Default, Debug)]
+enum BotPhase {
+    #[default]
+    Rowing,
+    Fishing,
+    Returning,
+}
+
+#[derive(Resource, Default)]
+struct Bot {
+    phase: BotPhase,
+    target: f32,
+    /// Time spent in the current phase.
+    elapsed: f32,
+    since_reel: f32,
+    soaked: f32,
+    cast: bool,
+}
+
+impl Bot {
+    fn enter(&mut self, phase: BotPhase) {
+        self.phase = phase;
+        self.elapsed = 0.;
+        self.since_reel = 0.;
+        self.soaked = 0.;
+        self.cast = false;
+    }
+}
+
+/// Plays the game by pressing the same actions as the keyboard: row to a spot, cast, reel in whatever
+/// bites, and head back to port to sell and shop once the hold fills up.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+fn bot(
+    time: Res<Time>,
+    mut bot: ResMut<Bot>,
+    mut actions: ResMut<Input<GameAction>>,
+    mut ev_port_collision: EventReader<PortCollisionEvent>,
+    mut ev_upgrade: EventWriter<UpgradeEvent>,
+    catalog: Res<ShopCatalog>,
+    map: Res<WorldMap>,
+    mut port: ResMut<PortStorage>,
+    mut player_query: Query<
+        (
+            &Transform,
+            &mut RodVariant,
+            &mut FishStorage,
+            &mut BoatSpeed,
+            &mut IceBox,
+        ),
+        With<Player>,
+    >,
+    rod_query: Query<(&Transform, &LineTension), (With<Rod>, Without<Player>)>,
+    fish_query: Query<&FishState, With<Fish>>,
+    mut rng: ResMut<GameRng>,
+) {
+    let delta = time.delta_seconds();
+    let docked = ev_port_collision.read().count() > 0;
+    let Ok((player, mut rod_variant, mut storage, mut boat, mut ice_box)) =
+        player_query.get_single_mut()
+    else {
+        return;
+    };
+
+    actions.release_all();
+    bot.elapsed += delta;
+    bot.since_reel += delta;
+
+    match bot.phase {
+        BotPhase::Rowing => {
+            let distance = bot.target - player.translation.x;
+
+            if distance.abs() < 5. {
+                bot.enter(BotPhase::Fishing);
+            } else if distance < 0. {
+                actions.press(GameAction::MoveLeft);
+            } else {
+                actions.press(GameAction::MoveRight);
+            }
+        }
+        BotPhase::Fishing => {
+            let Ok((hook, tension)) = rod_query.get_single() else {
+                if !bot.cast {
+                    actions.press(GameAction::Cast);
+                    bot.cast = true;
+                    bot.elapsed = 0.;
+                } else if bot.elapsed > 0.5 {
+                    // The hook has made it back to the boat
+                    if storage.current >= storage.max * RETURN_AT_CAPACITY {
+                        bot.enter(BotPhase::Returning);
+                    } else {
+                        bot.target = fishing_spot(player.translation.x, &map, &boat, &mut rng);
+                        bot.enter(BotPhase::Rowing);
+                    }
+                }
+                return;
+            };
+
+            let hooked = fish_query.iter().any(|state| *state == FishState::Hooked);
+            let caught = fish_query.iter().any(|state| *state == FishState::Caught);
+            let rod_length = rod_variant.get_rod_properties().length;
+
+            if hook.translation.y <= -rod_length + 1. {
+                bot.soaked += delta;
+            }
+
+            let reel = if hooked {
+                tension.current < SAFE_TENSION
+            } else {
+                caught || bot.soaked > SOAK_TIME
+            };
+
+            if reel && bot.since_reel > REEL_INTERVAL {
+                actions.press(GameAction::Reel);
+                bot.since_reel = 0.;
+            }
+        }
+        BotPhase::Returning => {
+            if !docked {
+                actions.press(GameAction::MoveLeft);
+                return;
+            }
+
+            // Wait a moment for the catch to be sold before shopping
+            if bot.elapsed < 0.5 {
+                return;
+            }
+
+            // Keep buying the cheapest upgrade on offer until broke
+            while let Some(ladder) = catalog
+                .ladders
+                .iter()
+                .filter(|ladder| {
+                    ladder
+                        .next_tier(&rod_variant, &storage, &boat, &ice_box)
+                        .is_some_and(|tier| tier.cost <= port.coins)
+                })
+                .min_by_key(|ladder| {
+                    ladder
+                        .next_tier(&rod_variant, &storage, &boat, &ice_box)
+                        .map(|tier| tier.cost)
+                })
+            {
+                ladder.buy_next(
+                    &mut port.coins,
+                    &mut rod_variant,
+                    &mut storage,
+                    &mut boat,
+                    &mut ice_box,
+                );
+                ev_upgrade.send_default();
+            }
+
+            bot.target = fishing_spot(player.translation.x, &map, &boat, &mut rng);
+            bot.enter(BotPhase::Rowing);
+        }
+    }
+}
+
+fn fishing_spot(from: f32, map: &WorldMap, boat: &BoatSpeed, rng: &mut GameRng) -> f32 {
+    let spot = from + rng.stream("bot").gen_range(-SPOT_SPREAD..SPOT_SPREAD);
+    spot.clamp(NEAREST_SPOT, map.reach(boat.0) - SWELL_MARGIN)
+}
+
+fn record_rods(
+    time: Res<Time>,
+    mut simulation: ResMut<Simulation>,
+    rod_query: Query<&RodVariant, (With<Player>, Changed<RodVariant>)>,
+) {
+    for rod in &rod_query {
+        if !simulation.rods.iter().any(|(variant, _)| variant == rod) {
+            simulation.rods.push((*rod, time.elapsed_seconds()));
+        }
+    }
+}
+
+fn record_events(
+    mut simulation: ResMut<Simulation>,
+    mut ev_catch_fish: EventReader<CatchFishEvent>,
+    mut ev_trash_collision: EventReader<TrashCollisionEvent>,
+    mut ev_line_snap: EventReader<LineSnapEvent>,
+    mut ev_drop_fish: EventReader<DropFishEvent>,
+    mut ev_deposit: EventReader<DepositFishEvent>,
+    mut ev_contract_completed: EventReader<ContractCompletedEvent>,
+) {
+    for catch in ev_catch_fish.read() {
+        let stats = simulation.catches.entry(catch.fish_variant).or_default();
+        stats.count += 1;
+        stats.total_weight += catch.weight.current;
+    }
+
+    simulation.trash_drops += ev_trash_collision.read().count() as u32;
+    simulation.line_snaps += ev_line_snap.read().count() as u32;
+    simulation.hold_full_drops += ev_drop_fish.read().count() as u32;
+
+    for deposit in ev_deposit.read() {
+        simulation.deposits += 1;
+        simulation.coins_earned += deposit.coins_earned;
+    }
+
+    for ev in ev_contract_completed.read() {
+        simulation.contracts += 1;
+        simulation.contract_coins += ev.contract.reward;
+    }
+}
+
+fn finish(time: Res<Time>, simulation: Res<Simulation>, mut ev_app_exit: EventWriter<AppExit>) {
+    if time.elapsed_seconds() < simulation.duration {
+        return;
+    }
+
+    println!("Simulated {:.1} minutes\n", simulation.duration / 60.);
+
+    println!("Rods unlocked:");
+    for (rod, at) in &simulation.rods {
+        println!("  {:<24} {}", format!("{rod:?}"), format_time(*at));
+    }
+
+    println!("\nCatches:");
+    for variant in FishVariant::iterator() {
+        let (count, average) = simulation.catches.get(variant).map_or((0, 0.), |stats| {
+            (stats.count, stats.total_weight / stats.count as f32)
+        });
+        println!(
+            "  {:<8} {:>5}  avg {:>7.2} kg",
+            format!("{variant:?}"),
+            count,
+            average
+        );
+    }
+
+    println!("\nFish lost to trash:       {}", simulation.trash_drops);
+    println!("Lines snapped:            {}", simulation.line_snaps);
+    println!("Fish dropped (hold full): {}", simulation.hold_full_drops);
+    println!(
+        "Deposits:                 {} ({} coins)",
+        simulation.deposits, simulation.coins_earned
+    );
+    println!(
+        "Contracts done:           {} ({} coins)",
+        simulation.contracts, simulation.contract_coins
+    );
+
+    ev_app_exit.send(AppExit);
+}
+
+fn format_time(seconds: f32) -> String {
+    let seconds = seconds as u32;
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
@@ -1,7 +1,11 @@
+use crate::clock::{Backdrop, Sky};
 use crate::components::CameraShake;
+use crate::player::Player;
 use crate::port::Port;
+use crate::resources::GameRng;
 use crate::rod::Rod;
-use crate::GameState::Game;
+use crate::weather::Weather;
+use crate::{GameState::Game, GameplaySet};
 use bevy::prelude::*;
 use noise::{NoiseFn, Perlin};
 use rand::Rng;
@@ -10,11 +14,24 @@ pub struct CameraPlugin;
 
 const MAX_SHAKE_OFFSET: f32 = 50.;
 const MAX_SHAKE_ANGLE: f32 = 5.;
+/// How far the camera rolls with the waves, in radians per px of wave
+/// height.
+const SWAY_PER_WAVE: f32 = 0.004;
+const SWAY_SPEED: f32 = 0.8;
+/// How quickly the camera catches up with the boat, per second.
+const FOLLOW_RATE: f32 = 4.;
 
 impl Plugin for CameraPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(Startup, setup)
-            .add_systems(Update, ((camera, shake_camera).run_if(in_state(Game)),));
+            .add_systems(
+                Update,
+                (camera, shake_camera, sway_camera, keep_sky_in_view)
+                    .chain()
+                    .in_set(GameplaySet::Camera)
+                    .run_if(in_state(Game)),
+            )
+            .add_systems(OnExit(Game), reset_camera);
     }
 }
 
@@ -22,16 +39,37 @@ fn setup(mut commands: Commands) {
     commands.spawn(Camera2dBundle::default());
 }
 
+/// Put the camera back where it started, for the menu and the next session.
+fn reset_camera(
+    mut commands: Commands,
+    mut camera_query: Query<(Entity, &mut Transform, &mut OrthographicProjection), With<Camera2d>>,
+) {
+    let (camera_entity, mut camera_transform, mut camera) = camera_query.single_mut();
+    let default = Camera2dBundle::default();
+
+    *camera_transform = default.transform;
+    *camera = default.projection;
+    commands.entity(camera_entity).remove::<CameraShake>();
+}
+
+#[allow(clippy::type_complexity)]
 fn camera(
     time: Res<Time>,
     mut camera_query: Query<(&mut Transform, &mut OrthographicProjection), With<Camera2d>>,
+    player_query: Query<&Transform, (With<Player>, Without<Camera2d>)>,
     rod_query: Query<&Transform, (With<Rod>, Without<Camera2d>, Without<Port>)>,
     port_query: Query<&Transform, (With<Port>, Without<Rod>, Without<Camera2d>)>,
 ) {
+    let player = player_query.single();
     let rod = rod_query.get_single();
     let port = port_query.single();
     let (mut camera_transform, mut camera) = camera_query.single_mut();
 
+    // Trail after the boat, catching up more the further behind it is
+    let follow = 1. - (-FOLLOW_RATE * time.delta_seconds()).exp();
+    camera_transform.translation.x +=
+        (player.translation.x - camera_transform.translation.x) * follow;
+
     let diff = port.translation.x - camera_transform.translation.x;
     camera.scale = (diff.abs() / 1000.) + 0.5;
     camera.scale = camera.scale.clamp(0.5, 3.);
@@ -53,6 +91,7 @@ fn shake_camera(
     mut commands: Commands,
     time: Res<Time>,
     mut camera_query: Query<(&mut Transform, Entity, &mut CameraShake), With<Camera2d>>,
+    mut rng: ResMut<GameRng>,
 ) {
     if camera_query.is_empty() {
         return;
@@ -66,7 +105,7 @@ fn shake_camera(
     let perlin = Perlin::new(30);
 
     // Generate a random Vector3
-    let mut rng = rand::thread_rng();
+    let rng = rng.stream("camera_shake");
     let random_vector = [
         rng.gen_range(-100.0..100.0),
         rng.gen_range(-100.0..100.0),
@@ -89,7 +128,40 @@ fn shake_camera(
     if camera_shake.shake_timer.tick(time.delta()).finished() {
         commands.entity(camera_entity).remove::<CameraShake>();
         camera_transform.rotation = Quat::IDENTITY;
-        camera_transform.translation = camera_shake.start_translation;
+        // The camera catches back up with the boat on its own
+        camera_transform.translation.y = camera_shake.start_translation.y;
+    }
+}
+
+/// Roll the camera with the waves. A shake puts the rotation back itself
+/// when it's over, so leave the camera to it until then.
+fn sway_camera(
+    time: Res<Time>,
+    weather: Res<Weather>,
+    mut camera_query: Query<&mut Transform, (With<Camera2d>, Without<CameraShake>)>,
+) {
+    let Ok(mut camera_transform) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    camera_transform.rotation = Quat::from_rotation_z(
+        weather.waves * SWAY_PER_WAVE * (time.elapsed_seconds() * SWAY_SPEED).sin(),
+    );
+}
+
+/// The sky and the horizon hang behind everything, so they stay put in the
+/// view while the camera follows the boat.
+#[allow(clippy::type_complexity)]
+fn keep_sky_in_view(
+    camera_query: Query<&Transform, With<Camera2d>>,
+    mut sky_query: Query<&mut Transform, (Or<(With<Sky>, With<Backdrop>)>, Without<Camera2d>)>,
+) {
+    let Ok(camera_transform) = camera_query.get_single() else {
+        return;
+    };
+
+    for mut transform in &mut sky_query {
+        transform.translation.x = camera_transform.translation.x;
     }
 }
 
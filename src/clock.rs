@@ -0,0 +1,270 @@
+use crate::{game::OnGameScreen, location::Location, GameState::Game, GameplaySet};
+use bevy::prelude::*;
+use serde::Deserialize;
+
+/// Real seconds a whole in-game day takes.
+const DAY_LENGTH: f32 = 600.;
+pub const HOURS_PER_DAY: f32 = 24.;
+/// Sessions start in the morning.
+const START_HOUR: f32 = 8.;
+/// Hours it takes one sky to fade into the next.
+const FADE_HOURS: f32 = 1.;
+const NIGHT: HourSpan = HourSpan { from: 20., to: 5. };
+
+/// A stretch of the day, in hours since midnight. Wraps past midnight when
+/// `from` is later than `to`.
+#[derive(Deserialize, Clone, Copy, Debug)]
+pub struct HourSpan {
+    pub from: f32,
+    pub to: f32,
+}
+
+impl HourSpan {
+    pub fn contains(&self, hour: f32) -> bool {
+        if self.from <= self.to {
+            (self.from..self.to).contains(&hour)
+        } else {
+            hour >= self.from || hour < self.to
+        }
+    }
+}
+
+struct Phase {
+    /// Hour the phase's sky is fully in.
+    start: f32,
+    name: &'static str,
+    sky: &'static str,
+    water: Color,
+}
+
+/// Index of the phase with the water's untinted colour.
+const MIDDAY: usize = 3;
+
+/// The day's skies in order, starting from the first after midnight.
+const PHASES: [Phase; 8] = [
+    Phase {
+        start: 4.,
+        name: "Before dawn",
+        sky: "craftpix/clouds/clouds_2/1.png",
+        water: Color::rgb(0.42, 0.45, 0.7),
+    },
+    Phase {
+        start: 6.,
+        name: "Dawn",
+        sky: "craftpix/clouds/clouds_4/1.png",
+        water: Color::rgb(0.5, 0.55, 0.75),
+    },
+    Phase {
+        start: 8.,
+        name: "Morning",
+        sky: "craftpix/clouds/clouds_5/1.png",
+        water: Color::rgb(0.45, 0.58, 0.85),
+    },
+    Phase {
+        start: 11.,
+        name: "Midday",
+        sky: "craftpix/clouds/clouds_1/1.png",
+        water: Color::rgb(0.447, 0.529, 0.835),
+    },
+    Phase {
+        start: 15.,
+        name: "Afternoon",
+        sky: "craftpix/clouds/clouds_6/1.png",
+        water: Color::rgb(0.45, 0.5, 0.7),
+    },
+    Phase {
+        start: 18.,
+        name: "Dusk",
+        sky: "craftpix/clouds/clouds_7/1.png",
+        water: Color::rgb(0.55, 0.45, 0.65),
+    },
+    Phase {
+        start: 20.,
+        name: "Evening",
+        sky: "craftpix/clouds/clouds_8/1.png",
+        water: Color::rgb(0.35, 0.3, 0.6),
+    },
+    Phase {
+        start: 22.,
+        name: "Night",
+        sky: "craftpix/clouds/clouds_3/1.png",
+        water: Color::rgb(0.12, 0.15, 0.32),
+    },
+];
+
+/// Time of day in the game world, in hours since midnight, and how many
+/// days the game has gone on for.
+#[derive(Resource, Debug)]
+pub struct GameClock {
+    pub hour: f32,
+    /// Counted from 1.
+    pub day: u32,
+}
+
+impl Default for GameClock {
+    fn default() -> Self {
+        GameClock {
+            hour: START_HOUR,
+            day: 1,
+        }
+    }
+}
+
+/// An hour of the day as `HH:MM`.
+pub fn format_hour(hour: f32) -> String {
+    let minutes = (hour * 60.).round() as u32 % (24 * 60);
+    format!("{:02}:{:02}", minutes / 60, minutes % 60)
+}
+
+impl GameClock {
+    /// Hours since the game began, counted from midnight before the first
+    /// day.
+    pub fn hours(&self) -> f32 {
+        (self.day - 1) as f32 * HOURS_PER_DAY + self.hour
+    }
+
+    pub fn is_night(&self) -> bool {
+        NIGHT.contains(self.hour)
+    }
+
+    /// Index of the phase the day is in, and how far it has faded into the
+    /// next one, from 0 to 1.
+    fn phase(&self) -> (usize, f32) {
+        let current = PHASES
+            .iter()
+            .rposition(|phase| phase.start <= self.hour)
+            .unwrap_or(PHASES.len() - 1);
+        let next = (current + 1) % PHASES.len();
+        let until_next = (PHASES[next].start - self.hour).rem_euclid(HOURS_PER_DAY);
+
+        (current, 1. - (until_next / FADE_HOURS).min(1.))
+    }
+
+    pub fn describe(&self) -> String {
+        format!(
+            "Day {} {} {}",
+            self.day,
+            format_hour(self.hour),
+            PHASES[self.phase().0].name
+        )
+    }
+}
+
+/// The sky the day is in, and the one it fades into on top of it.
+#[derive(Component)]
+pub struct Sky {
+    next: bool,
+}
+
+/// The body of water and the waves on top of it, tinted with the time of
+/// day.
+#[derive(Component)]
+pub struct Water;
+
+/// The location's far-off horizon, in front of the sky. Fades out with the
+/// daylight.
+#[derive(Component)]
+pub struct Backdrop;
+
+#[derive(Resource)]
+struct SkyImages(Vec<Handle<Image>>);
+
+pub struct ClockPlugin;
+
+impl Plugin for ClockPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameClock>()
+            .add_systems(OnEnter(Game), setup)
+            .add_systems(
+                Update,
+                (advance_clock, update_sky)
+                    .chain()
+                    .in_set(GameplaySet::Clock)
+                    .run_if(in_state(Game)),
+            );
+    }
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let images = SkyImages(
+        PHASES
+            .iter()
+            .map(|phase| asset_server.load(phase.sky))
+            .collect(),
+    );
+
+    for (next, z) in [(false, -20.), (true, -19.5)] {
+        commands.spawn((
+            SpriteBundle {
+                texture: images.0[0].clone(),
+                transform: Transform {
+                    translation: Vec3::new(0., 0., z),
+                    scale: Vec3::splat(7.),
+                    ..default()
+                },
+                ..default()
+            },
+            Sky { next },
+            OnGameScreen,
+        ));
+    }
+
+    commands.insert_resource(images);
+}
+
+fn advance_clock(time: Res<Time>, mut clock: ResMut<GameClock>) {
+    let hour = clock.hour + time.delta_seconds() / DAY_LENGTH * HOURS_PER_DAY;
+    if hour >= HOURS_PER_DAY {
+        clock.day += 1;
+    }
+    clock.hour = hour.rem_euclid(HOURS_PER_DAY);
+}
+
+/// Show the sky for the time of day, fading into the next one as it nears,
+/// and tint the water to match.
+#[allow(clippy::type_complexity)]
+fn update_sky(
+    clock: Res<GameClock>,
+    images: Res<SkyImages>,
+    location: Res<Location>,
+    mut sky_query: Query<(&mut Handle<Image>, &mut Sprite, &Sky)>,
+    mut water_query: Query<&mut Sprite, (With<Water>, Without<Sky>)>,
+    mut wave_query: Query<&mut TextureAtlasSprite, With<Water>>,
+    mut backdrop_query: Query<&mut Sprite, (With<Backdrop>, Without<Sky>, Without<Water>)>,
+) {
+    let (current, fade) = clock.phase();
+    let next = (current + 1) % PHASES.len();
+
+    for (mut image, mut sprite, sky) in &mut sky_query {
+        let (phase, alpha) = if sky.next {
+            (next, fade)
+        } else {
+            (current, 1.)
+        };
+
+        if *image != images.0[phase] {
+            *image = images.0[phase].clone();
+        }
+        sprite.color.set_a(alpha);
+    }
+
+    let water = Vec4::from(PHASES[current].water.as_rgba_f32())
+        .lerp(Vec4::from(PHASES[next].water.as_rgba_f32()), fade);
+    let location_tint = location.0.water_tint();
+    for mut sprite in &mut water_query {
+        sprite.color = Color::from(water * location_tint);
+    }
+
+    // The waves are drawn in their own colours, so darken them by as much
+    // as the water has been, and colour them like the location's water
+    let midday = Vec4::from(PHASES[MIDDAY].water.as_rgba_f32());
+    let daylight = (water / midday).min(Vec4::ONE);
+    let tint = Color::from(daylight * location_tint);
+    for mut sprite in &mut wave_query {
+        sprite.color = tint;
+    }
+
+    for mut sprite in &mut backdrop_query {
+        sprite.color.set_a(daylight.truncate().min_element());
+    }
+}
@@ -1,12 +1,13 @@
 use bevy::prelude::*;
 use rand::Rng;
+use serde::{Deserialize, Serialize};
 
 #[derive(Component)]
 pub struct Speed {
     pub current: f32,
 }
 
-#[derive(Component, Clone, Copy, Debug)]
+#[derive(Component, Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct Weight {
     pub current: f32,
 }
@@ -20,15 +21,15 @@ pub enum Direction {
 }
 
 impl Direction {
-    pub fn random_y() -> Self {
-        match rand::random::<bool>() {
+    pub fn random_y<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        match rng.gen::<bool>() {
             true => Self::Left,
             false => Self::Right,
         }
     }
 
-    pub fn random_x() -> Self {
-        match rand::random::<bool>() {
+    pub fn random_x<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        match rng.gen::<bool>() {
             true => Self::Up,
             false => Self::Down,
         }
@@ -60,6 +61,11 @@ impl FishStorage {
     }
 }
 
+/// Unscaled size of an entity's collision box. Matches the sprite's image
+/// size so collisions don't have to wait on the image to load.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Hitbox(pub Vec2);
+
 #[derive(Component)]
 pub struct Invincibility {
     pub invincibility_timer: Timer,
@@ -79,7 +85,7 @@ pub struct AnimationIndices {
 }
 
 #[derive(Component, Deref, DerefMut)]
-pub struct AnimationTimer(pub(crate) Timer);
+pub struct AnimationTimer(pub Timer);
 
 #[derive(Component)]
 pub struct CanDie {
@@ -91,9 +97,9 @@ pub struct DecayTimer {
     pub timer: Timer,
 }
 
-impl Default for DecayTimer {
-    fn default() -> Self {
-        let time_to_death = rand::thread_rng().gen_range(30. ..120.);
+impl DecayTimer {
+    pub fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let time_to_death = rng.gen_range(30. ..120.);
         DecayTimer {
             timer: Timer::from_seconds(time_to_death, TimerMode::Once),
         }
@@ -108,6 +114,3 @@ pub struct Acceleration(pub(crate) Vec3);
 
 #[derive(Component)]
 pub struct BGMPlayer;
-
-#[derive(Component)]
-pub struct PauseMenu;
@@ -0,0 +1,526 @@
+use crate::{
+    clock::{format_hour, GameClock, HOURS_PER_DAY},
+    components::Weight,
+    despawn_screen,
+    events::{ContractCompletedEvent, PortCollisionEvent},
+    fish::{FishRegistry, FishVariant},
+    location::Location,
+    port::check_for_port_collisions,
+    resources::{load_data_file, GameRng, StoredFish},
+    GameState::Game,
+    GameplaySet, TEXT_COLOR,
+};
+use bevy::prelude::*;
+use rand::{distributions::WeightedIndex, prelude::Distribution, Rng};
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+/// How contracts are posted on the board, loaded from
+/// `assets/data/contracts.ron`.
+#[derive(Resource, Deserialize, Debug)]
+pub struct ContractRules {
+    pub board_size: usize,
+    pub refresh_hours: f32,
+    pub deadline_hours: Range<f32>,
+    pub deep_chance: f64,
+    pub deliver_count: Range<u32>,
+    /// How far up its species' weight range each fish has to be.
+    pub deliver_weight: Range<f32>,
+    /// Reward as a multiple of what the fish would sell for.
+    pub deliver_bonus: f32,
+    pub deep_count: Range<u32>,
+    /// How far down towards the bottom the fish have to be caught.
+    pub deep_depth: Range<f32>,
+    /// Coins per fish for each px below the surface.
+    pub deep_reward: f32,
+}
+
+impl ContractRules {
+    pub const PATH: &'static str = "data/contracts.ron";
+
+    pub fn load() -> Self {
+        load_data_file(Self::PATH)
+    }
+
+    /// Draw up a contract for fish found at `location`, due `now` plus the
+    /// deadline.
+    fn generate(
+        &self,
+        registry: &FishRegistry,
+        location: &Location,
+        now: f32,
+        rng: &mut impl Rng,
+    ) -> Contract {
+        let due = now + rng.gen_range(self.deadline_hours.clone());
+        let variants: Vec<FishVariant> = FishVariant::iterator().copied().collect();
+        let species = WeightedIndex::new(
+            variants
+                .iter()
+                .map(|variant| location.0.spawn_weight(*variant)),
+        );
+
+        match species {
+            Ok(species) if !rng.gen_bool(self.deep_chance) => {
+                let species = registry.get(variants[species.sample(rng)]);
+                let count = rng.gen_range(self.deliver_count.clone());
+                let fraction = rng.gen_range(self.deliver_weight.clone());
+                let weight =
+                    species.weight.start + (species.weight.end - species.weight.start) * fraction;
+                // Round down so the weight asked for reads cleanly
+                let weight = (weight * 10.).floor() / 10.;
+                let price = species.sale_price(&Weight { current: weight });
+
+                Contract {
+                    goal: ContractGoal::Deliver {
+                        variant: species.variant,
+                        weight,
+                    },
+                    count,
+                    delivered: 0,
+                    reward: (count as f32 * price as f32 * self.deliver_bonus).round() as u32,
+                    due,
+                }
+            }
+            _ => {
+                let count = rng.gen_range(self.deep_count.clone());
+                let depth = location.0.depth * rng.gen_range(self.deep_depth.clone());
+                let depth = (depth / 100.).round().max(1.) * 100.;
+
+                Contract {
+                    goal: ContractGoal::Deep { depth },
+                    count,
+                    delivered: 0,
+                    reward: (count as f32 * depth * self.deep_reward).round().max(1.) as u32,
+                    due,
+                }
+            }
+        }
+    }
+}
+
+/// What a contract wants delivered.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub enum ContractGoal {
+    /// Fish of this variant, each at least `weight` kg.
+    Deliver { variant: FishVariant, weight: f32 },
+    /// Fish of any kind, caught at least `depth` px down.
+    Deep { depth: f32 },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct Contract {
+    pub goal: ContractGoal,
+    /// Fish it takes.
+    pub count: u32,
+    /// Fish handed over towards it so far.
+    pub delivered: u32,
+    /// Coins paid once it's done.
+    pub reward: u32,
+    /// Hours since the game began it has to be done by.
+    pub due: f32,
+}
+
+impl Contract {
+    fn wants(&self, fish: &StoredFish) -> bool {
+        if self.is_done() {
+            return false;
+        }
+
+        match self.goal {
+            ContractGoal::Deliver { variant, weight } => {
+                fish.variant == variant && fish.weight.current >= weight
+            }
+            ContractGoal::Deep { depth } => fish.depth >= depth,
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.delivered >= self.count
+    }
+
+    pub fn describe(&self, registry: &FishRegistry) -> String {
+        match self.goal {
+            ContractGoal::Deliver { variant, weight } => {
+                format!("{} over {:.1} kg", registry.get(variant).name, weight)
+            }
+            ContractGoal::Deep { depth } => format!("Fish caught below {depth:.0} px down"),
+        }
+    }
+
+    pub fn describe_due(&self) -> String {
+        let day = (self.due / HOURS_PER_DAY) as u32 + 1;
+        format!("day {} {}", day, format_hour(self.due % HOURS_PER_DAY))
+    }
+}
+
+/// Contracts posted at the fishing hut, kept with the save.
+#[derive(Resource, Serialize, Deserialize, Default, Debug, Clone)]
+pub struct ContractBoard {
+    pub contracts: Vec<Contract>,
+    /// Hours since the game began the board is next filled at. Not saved,
+    /// so a loaded game fills it straight away.
+    #[serde(skip)]
+    next_refresh: f32,
+}
+
+impl ContractBoard {
+    /// Count each fish towards the first contract wanting it, and take down
+    /// the contracts that are now done.
+    pub fn deliver(&mut self, fish: &[StoredFish]) -> Vec<Contract> {
+        for fish in fish {
+            if let Some(contract) = self
+                .contracts
+                .iter_mut()
+                .find(|contract| contract.wants(fish))
+            {
+                contract.delivered += 1;
+            }
+        }
+
+        let (done, open) = std::mem::take(&mut self.contracts)
+            .into_iter()
+            .partition(Contract::is_done);
+        self.contracts = open;
+        done
+    }
+}
+
+#[derive(Component)]
+struct ContractBoardUI;
+
+pub struct ContractsPlugin;
+
+impl Plugin for ContractsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ContractRules::load())
+            .init_resource::<ContractBoard>()
+            .add_event::<ContractCompletedEvent>()
+            .add_systems(
+                Update,
+                (
+                    refresh_board.before(check_for_port_collisions),
+                    toggle_board.after(check_for_port_collisions),
+                )
+                    .in_set(GameplaySet::Port)
+                    .run_if(in_state(Game)),
+            )
+            .add_systems(OnExit(Game), despawn_screen::<ContractBoardUI>);
+    }
+}
+
+/// Take down contracts that have run out of time, and fill the board back
+/// up every so often.
+fn refresh_board(
+    clock: Res<GameClock>,
+    rules: Res<ContractRules>,
+    registry: Res<FishRegistry>,
+    location: Res<Location>,
+    mut board: ResMut<ContractBoard>,
+    mut rng: ResMut<GameRng>,
+) {
+    let now = clock.hours();
+
+    if board.contracts.iter().any(|contract| contract.due <= now) {
+        board.contracts.retain(|contract| contract.due > now);
+    }
+
+    if now < board.next_refresh {
+        return;
+    }
+
+    board.next_refresh = now + rules.refresh_hours;
+    let rng = rng.stream("contracts");
+    while board.contracts.len() < rules.board_size {
+        let contract = rules.generate(&registry, &location, now, rng);
+        board.contracts.push(contract);
+    }
+}
+
+/// Show the board while the boat is docked at the port, kept up to date
+/// with deliveries.
+fn toggle_board(
+    mut commands: Commands,
+    mut ev_port_collision: EventReader<PortCollisionEvent>,
+    board_query: Query<Entity, With<ContractBoardUI>>,
+    board: Res<ContractBoard>,
+    registry: Res<FishRegistry>,
+    asset_server: Res<AssetServer>,
+) {
+    let docked = ev_port_collision.read().count() > 0;
+
+    if let Ok(ui) = board_query.get_single() {
+        if docked && !board.is_changed() {
+            return;
+        }
+
+        commands.entity(ui).despawn_recursive();
+    }
+
+    if docked {
+        spawn_board(&mut commands, &asset_server, &board, &registry);
+    }
+}
+
+fn spawn_board(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    board: &ContractBoard,
+    registry: &FishRegistry,
+) {
+    let text_style = TextStyle {
+        font: asset_server.load("fonts/Pixellari.ttf"),
+        font_size: 20.0,
+        color: TEXT_COLOR,
+    };
+    let detail_style = TextStyle {
+        font_size: 16.0,
+        ..text_style.clone()
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(10.0),
+                    right: Val::Px(10.0),
+                    width: Val::Px(360.0),
+                    padding: UiRect::all(Val::Px(10.0)),
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.6).into(),
+                ..default()
+            },
+            ContractBoardUI,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Contracts",
+                TextStyle {
+                    font_size: 30.0,
+                    ..text_style.clone()
+                },
+            ));
+
+            if board.contracts.is_empty() {
+                parent.spawn(TextBundle::from_section(
+                    "Nothing posted, check back later",
+                    detail_style.clone(),
+                ));
+            }
+
+            for contract in &board.contracts {
+                parent.spawn(
+                    TextBundle::from_section(
+                        format!(
+                            "{} - {}/{}",
+                            contract.describe(registry),
+                            contract.delivered,
+                            contract.count
+                        ),
+                        text_style.clone(),
+                    )
+                    .with_style(Style {
+                        margin: UiRect::top(Val::Px(6.0)),
+                        ..default()
+                    }),
+                );
+                parent.spawn(TextBundle::from_section(
+                    format!("{} coins, by {}", contract.reward, contract.describe_due()),
+                    detail_style.clone(),
+                ));
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::location::{LocationCatalog, LocationKind};
+
+    fn contract(goal: ContractGoal, count: u32) -> Contract {
+        Contract {
+            goal,
+            count,
+            delivered: 0,
+            reward: 10,
+            due: 100.,
+        }
+    }
+
+    fn fish(variant: FishVariant, weight: f32, depth: f32) -> StoredFish {
+        StoredFish {
+            variant,
+            weight: Weight { current: weight },
+            depth,
+            freshness: 1.,
+        }
+    }
+
+    fn reef() -> Location {
+        Location(LocationCatalog::load().get(LocationKind::Reef).clone())
+    }
+
+    #[test]
+    fn wants_fish_meeting_the_goal_until_done() {
+        let mut heavy = contract(
+            ContractGoal::Deliver {
+                variant: FishVariant::Two,
+                weight: 1.5,
+            },
+            1,
+        );
+        assert!(heavy.wants(&fish(FishVariant::Two, 1.5, 0.)));
+        assert!(!heavy.wants(&fish(FishVariant::Two, 1.4, 0.)));
+        assert!(!heavy.wants(&fish(FishVariant::Three, 3., 0.)));
+
+        let deep = contract(ContractGoal::Deep { depth: 300. }, 1);
+        assert!(deep.wants(&fish(FishVariant::One, 0.1, 300.)));
+        assert!(!deep.wants(&fish(FishVariant::One, 0.1, 299.)));
+
+        heavy.delivered = 1;
+        assert!(!heavy.wants(&fish(FishVariant::Two, 1.5, 0.)));
+    }
+
+    #[test]
+    fn deliver_counts_each_fish_towards_the_first_contract_wanting_it() {
+        let mut board = ContractBoard {
+            contracts: vec![
+                contract(
+                    ContractGoal::Deliver {
+                        variant: FishVariant::One,
+                        weight: 1.,
+                    },
+                    1,
+                ),
+                contract(
+                    ContractGoal::Deliver {
+                        variant: FishVariant::One,
+                        weight: 0.5,
+                    },
+                    3,
+                ),
+                contract(ContractGoal::Deep { depth: 100. }, 3),
+            ],
+            ..default()
+        };
+
+        // The first heavy fish fills the first contract, so the rest go to
+        // the second even though they'd all do for the deep one
+        let done = board.deliver(&[
+            fish(FishVariant::One, 2., 500.),
+            fish(FishVariant::One, 2., 500.),
+            fish(FishVariant::One, 0.7, 500.),
+            fish(FishVariant::Two, 2., 500.),
+        ]);
+
+        assert_eq!(done.len(), 1);
+        assert_eq!(done[0].delivered, 1);
+        let delivered: Vec<u32> = board.contracts.iter().map(|c| c.delivered).collect();
+        assert_eq!(delivered, [2, 1]);
+    }
+
+    #[test]
+    fn deliver_takes_down_done_contracts_and_keeps_the_rest() {
+        let mut board = ContractBoard {
+            contracts: vec![
+                contract(ContractGoal::Deep { depth: 100. }, 2),
+                contract(
+                    ContractGoal::Deliver {
+                        variant: FishVariant::Four,
+                        weight: 1.,
+                    },
+                    1,
+                ),
+                contract(
+                    ContractGoal::Deliver {
+                        variant: FishVariant::Five,
+                        weight: 1.,
+                    },
+                    2,
+                ),
+            ],
+            ..default()
+        };
+
+        let done = board.deliver(&[
+            fish(FishVariant::Four, 3., 0.),
+            fish(FishVariant::Five, 3., 0.),
+            fish(FishVariant::Six, 3., 150.),
+        ]);
+
+        assert_eq!(done.len(), 1);
+        assert!(matches!(
+            done[0].goal,
+            ContractGoal::Deliver {
+                variant: FishVariant::Four,
+                ..
+            }
+        ));
+
+        // Progress on the ones still open is kept, in the order posted
+        let open: Vec<(u32, u32)> = board
+            .contracts
+            .iter()
+            .map(|c| (c.delivered, c.count))
+            .collect();
+        assert_eq!(open, [(1, 2), (1, 2)]);
+    }
+
+    #[test]
+    fn generate_asks_for_species_found_at_the_location() {
+        let mut rules = ContractRules::load();
+        rules.deep_chance = 0.;
+        let registry = FishRegistry::load();
+        let mut location = reef();
+        location.0.species = [(FishVariant::Three, 1)].into_iter().collect();
+        let mut rng = GameRng::new(1);
+
+        for _ in 0..50 {
+            let contract = rules.generate(&registry, &location, 10., rng.stream("test"));
+
+            let ContractGoal::Deliver { variant, weight } = contract.goal else {
+                panic!(
+                    "expected a species to be asked for, got {:?}",
+                    contract.goal
+                );
+            };
+            assert_eq!(variant, FishVariant::Three);
+            // Rounded down to a tenth of a kg
+            let range = &registry.get(variant).weight;
+            assert!(weight > range.start - 0.1 && weight < range.end);
+            assert!(rules.deliver_count.contains(&contract.count));
+            assert!(contract.due >= 10. + rules.deadline_hours.start);
+            assert!(contract.due < 10. + rules.deadline_hours.end);
+        }
+    }
+
+    #[test]
+    fn generate_falls_back_to_deep_contracts_without_species() {
+        let mut rules = ContractRules::load();
+        rules.deep_chance = 0.;
+        let registry = FishRegistry::load();
+        let mut location = reef();
+        for weight in location.0.species.values_mut() {
+            *weight = 0;
+        }
+        let mut rng = GameRng::new(1);
+
+        for _ in 0..50 {
+            let contract = rules.generate(&registry, &location, 10., rng.stream("test"));
+
+            let ContractGoal::Deep { depth } = contract.goal else {
+                panic!(
+                    "expected deep fish to be asked for, got {:?}",
+                    contract.goal
+                );
+            };
+            assert!(depth >= 100.);
+            assert!(depth <= location.0.depth);
+            assert_eq!(depth % 100., 0.);
+            assert!(rules.deep_count.contains(&contract.count));
+            assert!(contract.reward > 0);
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use crate::components::Hitbox;
+use bevy::prelude::*;
+
+/// Toggles drawing every collision box over the game.
+const TOGGLE_COLLIDERS: KeyCode = KeyCode::F3;
+const COLLIDER_COLOR: Color = Color::LIME_GREEN;
+
+/// Whether collision boxes are drawn.
+#[derive(Resource, Default)]
+pub struct ShowColliders(pub bool);
+
+/// Overlays for tracking down gameplay bugs, on fixed keys rather than
+/// rebindable actions.
+pub struct DebugPlugin;
+
+impl Plugin for DebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ShowColliders>().add_systems(
+            Update,
+            (
+                toggle_colliders,
+                draw_colliders.run_if(|show: Res<ShowColliders>| show.0),
+            )
+                .chain(),
+        );
+    }
+}
+
+fn toggle_colliders(keys: Res<Input<KeyCode>>, mut show: ResMut<ShowColliders>) {
+    if keys.just_pressed(TOGGLE_COLLIDERS) {
+        show.0 = !show.0;
+    }
+}
+
+/// Draw hitboxes the way collisions see them: scaled by the entity but never
+/// rotated, and only offset by a parent's translation.
+fn draw_colliders(
+    mut gizmos: Gizmos,
+    collider_query: Query<(&Transform, &Hitbox, Option<&Parent>)>,
+    parent_query: Query<&Transform>,
+) {
+    for (transform, hitbox, parent) in &collider_query {
+        let offset = parent
+            .and_then(|parent| parent_query.get(parent.get()).ok())
+            .map_or(Vec3::ZERO, |parent| parent.translation);
+
+        gizmos.rect_2d(
+            (offset + transform.translation).truncate(),
+            0.,
+            hitbox.0 * transform.scale.truncate(),
+            COLLIDER_COLOR,
+        );
+    }
+}
@@ -1,5 +1,8 @@
 use crate::components::Weight;
+use crate::contracts::Contract;
 use crate::fish::FishVariant;
+use crate::location::LocationKind;
+use crate::records::Record;
 use bevy::prelude::*;
 use bevy::sprite::collide_aabb::Collision;
 
@@ -14,6 +17,9 @@ pub struct FishCollisionWithRodEvent {
 #[derive(Event, Default)]
 pub struct TrashCollisionEvent;
 
+#[derive(Event, Default)]
+pub struct LineSnapEvent;
+
 #[derive(Event)]
 pub struct PortCollisionEvent {
     pub collision_direction: Collision,
@@ -22,8 +28,10 @@ pub struct PortCollisionEvent {
 #[derive(Event, Default)]
 pub struct DepositFishEvent {
     pub port_weight: f32,
-    pub new_max: Option<f32>,
-    pub next_upgrade: Option<f32>,
+    /// Kg of fish handed over this time.
+    pub weight: f32,
+    pub coins_earned: u32,
+    pub coins: u32,
 }
 
 #[derive(Event, Default)]
@@ -32,9 +40,36 @@ pub struct DropFishEvent;
 #[derive(Event, Default)]
 pub struct WeightLimitEvent;
 
+/// The boat has made it as far out as it can, and the next fishing ground
+/// needs a faster one.
+#[derive(Event, Default)]
+pub struct RoughWaterEvent;
+
 #[derive(Event, Default)]
 pub struct UpgradeEvent;
 
+/// Buy the next tier of the shop ladder at this index in the catalog.
+#[derive(Event, Clone, Copy)]
+pub struct BuyUpgradeEvent {
+    pub ladder: usize,
+}
+
+/// Buy a pack of the bait at this index in the catalog.
+#[derive(Event, Clone, Copy)]
+pub struct BuyBaitEvent {
+    pub bait: usize,
+}
+
+/// Set off for another location from the sea chart.
+#[derive(Event, Clone, Copy)]
+pub struct TravelEvent {
+    pub location: LocationKind,
+}
+
+/// Leave the pause menu and carry on playing.
+#[derive(Event, Default)]
+pub struct ResumeEvent;
+
 #[derive(Event)]
 pub struct ReelingFishEvent {
     pub weight: Weight,
@@ -45,4 +80,25 @@ pub struct ReelingFishEvent {
 pub struct CatchFishEvent {
     pub weight: Weight,
     pub fish_variant: FishVariant,
+    /// Whether it was near the top of its species' weight range.
+    pub trophy: bool,
+    /// How far down it took the hook.
+    pub depth: f32,
+}
+
+#[derive(Event, Clone, Copy)]
+pub struct NewRecordEvent {
+    pub record: Record,
+}
+
+/// The achievement at this index in the catalog has just been unlocked.
+#[derive(Event, Clone, Copy)]
+pub struct AchievementUnlockedEvent {
+    pub index: usize,
+}
+
+/// A contract on the board has been delivered on and paid out.
+#[derive(Event, Clone, Copy)]
+pub struct ContractCompletedEvent {
+    pub contract: Contract,
 }
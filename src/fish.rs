@@ -1,25 +1,32 @@
 use crate::components::CameraShake;
-use crate::events::{CatchFishEvent, DropFishEvent, ReelingFishEvent, WeightLimitEvent};
+use crate::events::{
+    CatchFishEvent, DropFishEvent, LineSnapEvent, ReelingFishEvent, WeightLimitEvent,
+};
 use crate::{
+    bait::Lure,
+    clock::{GameClock, HourSpan},
     components::{
-        AnimationIndices, AnimationTimer, CanDie, DecayTimer, Direction, FishStorage,
-        Invincibility, Speed, Weight,
+        AnimationIndices, AnimationTimer, CanDie, DecayTimer, FishStorage, Hitbox, Invincibility,
+        Speed, Velocity, Weight,
     },
     events::{BoatCollisionEvent, FishCollisionWithRodEvent, TrashCollisionEvent},
-    player::Player,
-    resources::{AliveFish, PlayerFishStored},
+    game::OnGameScreen,
+    location::{Location, LocationSpec},
+    player::{Player, BOAT_START},
+    resources::{load_data_file, AliveFish, GameRng, PlayerFishStored, StoredFish},
     rod::Rod,
+    spatial::{rebuild_grid, SpatialGrid},
+    weather::{Weather, WeatherCatalog, WeatherSpec},
+    world::{FishingGround, WorldMap},
     GameState::Game,
+    GameplaySet,
 };
-use bevy::prelude::*;
-use rand::{
-    distributions::{Standard, WeightedIndex},
-    prelude::Distribution,
-    Rng,
-};
-use std::slice::Iter;
+use bevy::{prelude::*, utils::HashMap};
+use rand::{distributions::WeightedIndex, prelude::Distribution, Rng};
+use serde::{Deserialize, Serialize};
+use std::{ops::Range, slice::Iter};
 
-#[derive(Component, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[derive(Component, Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub enum FishVariant {
     One,
     Two,
@@ -45,130 +52,253 @@ impl FishVariant {
         ];
         FISH_VARIANTS.iter()
     }
+}
+
+/// Tuning for a single species, loaded from `assets/data/fish.ron`.
+#[derive(Deserialize, Debug, Clone)]
+pub struct FishSpecies {
+    pub variant: FishVariant,
+    pub name: String,
+    pub description: String,
+    pub sprite: String,
+    pub frame_size: (f32, f32),
+    pub frames: usize,
+    pub depth: Range<f32>,
+    /// Depth it rises or sinks to at night. The same as by day if `None`.
+    #[serde(default)]
+    pub night_depth: Option<Range<f32>>,
+    /// Hours of the day it's out and about. Always if `None`.
+    #[serde(default)]
+    pub active: Option<HourSpan>,
+    pub weight: Range<f32>,
+    pub speed: Range<f32>,
+    /// How many spawn together and swim as a school. Solitary if `None`.
+    pub school: Option<Range<u32>>,
+    /// Unscaled collision box, for sprites with a lot of empty space in
+    /// their frame. The whole frame if `None`.
+    #[serde(default)]
+    pub hitbox: Option<(f32, f32)>,
+    /// Coins paid per kg when sold at the port.
+    pub price_per_kg: f32,
+}
+
+impl FishSpecies {
+    pub fn is_active(&self, clock: &GameClock) -> bool {
+        self.active.is_none_or(|active| active.contains(clock.hour))
+    }
+
+    /// Depth band it swims in at this time of day, kept above the bottom.
+    pub fn depth(&self, clock: &GameClock, bottom: f32) -> Range<f32> {
+        let band = match &self.night_depth {
+            Some(night_depth) if clock.is_night() => night_depth,
+            _ => &self.depth,
+        };
+        let end = band.end.min(bottom);
+
+        band.start.min(end - 1.)..end
+    }
+
+    pub fn sale_price(&self, weight: &Weight) -> u32 {
+        (self.price_per_kg * weight.current).round().max(1.) as u32
+    }
+
+    /// Where `weight` falls in this species' range, from 0 for the lightest
+    /// to 1 for the heaviest.
+    pub fn weight_fraction(&self, weight: &Weight) -> f32 {
+        let span = self.weight.end - self.weight.start;
+        if span <= 0. {
+            return 0.;
+        }
+
+        ((weight.current - self.weight.start) / span).clamp(0., 1.)
+    }
+
+    /// How big to draw a fish of this weight, and so how big its hitbox is.
+    pub fn scale(&self, weight: &Weight) -> f32 {
+        FISH_MIN_SCALE + (FISH_MAX_SCALE - FISH_MIN_SCALE) * self.weight_fraction(weight)
+    }
+
+    pub fn is_trophy(&self, weight: &Weight) -> bool {
+        self.weight_fraction(weight) >= TROPHY_FRACTION
+    }
+
+    pub fn hitbox(&self) -> Hitbox {
+        let (width, height) = self.hitbox.unwrap_or(self.frame_size);
+        Hitbox(Vec2::new(width, height))
+    }
+
+    pub fn texture_atlas(&self, asset_server: &AssetServer) -> (TextureAtlas, AnimationIndices) {
+        let texture_handle = asset_server.load(&self.sprite);
+        let (width, height) = self.frame_size;
 
-    pub fn texture_atlas(self, asset_server: AssetServer) -> (TextureAtlas, AnimationIndices) {
         (
-            match self {
-                FishVariant::One => {
-                    let texture_handle = asset_server.load("craftpix/objects/Catch/1.png");
-                    TextureAtlas::from_grid(texture_handle, Vec2::new(12., 6.), 2, 1, None, None)
-                }
-                FishVariant::Two => {
-                    let texture_handle = asset_server.load("craftpix/objects/Catch/2.png");
-                    TextureAtlas::from_grid(texture_handle, Vec2::new(16., 12.), 2, 1, None, None)
-                }
-                FishVariant::Three => {
-                    let texture_handle = asset_server.load("craftpix/objects/Catch/3.png");
-                    TextureAtlas::from_grid(texture_handle, Vec2::new(20., 12.), 2, 1, None, None)
-                }
-                FishVariant::Four => {
-                    let texture_handle = asset_server.load("craftpix/objects/Catch/4.png");
-                    TextureAtlas::from_grid(texture_handle, Vec2::new(26., 12.), 2, 1, None, None)
-                }
-                FishVariant::Five => {
-                    let texture_handle = asset_server.load("craftpix/objects/Catch/5.png");
-                    TextureAtlas::from_grid(texture_handle, Vec2::new(30., 12.), 2, 1, None, None)
-                }
-                FishVariant::Six => {
-                    let texture_handle = asset_server.load("craftpix/objects/Catch/6.png");
-                    TextureAtlas::from_grid(texture_handle, Vec2::new(54., 22.), 2, 1, None, None)
-                }
-                FishVariant::Seven => {
-                    let texture_handle = asset_server.load("craftpix/objects/Catch/7.png");
-                    TextureAtlas::from_grid(texture_handle, Vec2::new(30., 12.), 2, 1, None, None)
-                }
-                FishVariant::Eight => {
-                    let texture_handle = asset_server.load("craftpix/objects/Catch/8.png");
-                    TextureAtlas::from_grid(texture_handle, Vec2::new(28., 24.), 2, 1, None, None)
-                }
+            TextureAtlas::from_grid(
+                texture_handle,
+                Vec2::new(width, height),
+                self.frames,
+                1,
+                None,
+                None,
+            ),
+            AnimationIndices {
+                first: 0,
+                last: self.frames - 1,
             },
-            AnimationIndices { first: 0, last: 1 },
         )
     }
+}
 
-    pub fn get_spawn_depth_range(self) -> std::ops::Range<f32> {
-        match self {
-            FishVariant::One => 100.0..200.,
-            FishVariant::Two => 150.0..500.,
-            FishVariant::Three => 400.0..800.,
-            FishVariant::Four => 700.0..1000.,
-            FishVariant::Five => 950.0..1300.,
-            FishVariant::Six => 100.0..2000.,
-            FishVariant::Seven => 1600.0..2000.,
-            FishVariant::Eight => 2300.0..2800.,
-        }
+/// Every fish species the game knows about. Designers can rebalance or
+/// reskin fish by editing the species file without touching the code.
+#[derive(Resource, Deserialize, Debug)]
+pub struct FishRegistry {
+    pub species: Vec<FishSpecies>,
+}
+
+impl FishRegistry {
+    pub const PATH: &'static str = "data/fish.ron";
+
+    pub fn load() -> Self {
+        load_data_file(Self::PATH)
     }
 
-    pub fn get_weight_range(self) -> std::ops::Range<f32> {
-        match self {
-            FishVariant::One => 0.1..0.3,
-            FishVariant::Two => 0.3..1.,
-            FishVariant::Three => 3. ..6.,
-            FishVariant::Four => 6. ..15.,
-            FishVariant::Five => 15. ..25.,
-            FishVariant::Six => 100. ..500.,
-            FishVariant::Seven => 35. ..70.,
-            FishVariant::Eight => 80. ..150.,
-        }
+    pub fn get(&self, variant: FishVariant) -> &FishSpecies {
+        self.species
+            .iter()
+            .find(|species| species.variant == variant)
+            .expect("every fish variant to have a species definition")
     }
-}
 
-impl Distribution<FishVariant> for Standard {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> FishVariant {
-        match rng.gen_range(0..=7) {
-            0 => FishVariant::One,
-            1 => FishVariant::Two,
-            2 => FishVariant::Three,
-            3 => FishVariant::Four,
-            4 => FishVariant::Five,
-            5 => FishVariant::Six,
-            6 => FishVariant::Seven,
-            _ => FishVariant::Eight,
-        }
+    /// Picks a species out at this time of day at random, favouring those
+    /// with a higher spawn weight at this location, in this weather and
+    /// fishing ground. `None` if none of them can spawn, as when the
+    /// weather has driven every species at the location off.
+    pub fn weighted_random<R: Rng + ?Sized>(
+        &self,
+        clock: &GameClock,
+        location: &LocationSpec,
+        weather: &WeatherSpec,
+        ground: &FishingGround,
+        rng: &mut R,
+    ) -> Option<&FishSpecies> {
+        let dist = WeightedIndex::new(self.species.iter().map(|species| {
+            if species.is_active(clock) {
+                location.spawn_weight(species.variant) as f32
+                    * weather.spawn_multiplier(species.variant)
+                    * ground.spawn_multiplier(species.variant)
+            } else {
+                0.
+            }
+        }))
+        .ok()?;
+
+        Some(&self.species[dist.sample(rng)])
     }
 }
 
 #[derive(Component, Debug, PartialEq)]
 pub enum FishState {
     Swimming,
+    /// On the hook and fighting the line.
+    Hooked,
+    /// Worn out, and being reeled in without a fight.
     Caught,
 }
 
+/// How much fight a hooked fish has left. Heavier fish take longer to tire.
+#[derive(Component, Debug)]
+pub struct FishStamina {
+    pub current: f32,
+    pub max: f32,
+}
+
+impl FishStamina {
+    pub fn from_weight(weight: &Weight) -> Self {
+        let max = FISH_STAMINA_BASE + weight.current.sqrt() * FISH_STAMINA_PER_ROOT_KG;
+
+        FishStamina { current: max, max }
+    }
+
+    pub fn fraction(&self) -> f32 {
+        (self.current / self.max).clamp(0., 1.)
+    }
+}
+
 #[derive(Component)]
 pub struct Fish;
 
+/// A fish near the top of its species' weight range.
+#[derive(Component)]
+pub struct Trophy;
+
+/// How far down a fish was when it took the hook.
+#[derive(Component)]
+pub struct HookDepth(pub f32);
+
+/// How a fish steers while it's free to swim.
+#[derive(Component, Debug)]
+pub struct FishSteering {
+    /// Swims along with nearby fish of its species rather than alone.
+    pub schooling: bool,
+    /// The band below the surface the fish keeps to.
+    pub depth: Range<f32>,
+    /// The pitch the fish is idly drifting towards, in radians.
+    wander: f32,
+}
+
 #[derive(Bundle)]
 struct FishBundle {
     marker: Fish,
-    direction: Direction,
+    velocity: Velocity,
+    steering: FishSteering,
     speed: Speed,
     weight: Weight,
     state: FishState,
     variant: FishVariant,
+    hitbox: Hitbox,
     sprite_sheet: SpriteSheetBundle,
     can_die: CanDie,
     decay_timer: DecayTimer,
 }
 
-impl Default for FishBundle {
-    fn default() -> Self {
-        FishBundle {
-            marker: Fish,
-            direction: Direction::Left,
-            speed: Speed { current: 200. },
-            weight: Weight { current: 0.1 },
-            state: FishState::Swimming,
-            variant: FishVariant::One,
-            can_die: CanDie { dying: false },
-            sprite_sheet: Default::default(),
-            decay_timer: Default::default(),
-        }
-    }
-}
-
 const FISH_INVINCIBILITY_TIME: f32 = 1.;
-const FISH_SPEED_MIN: f32 = 150.;
-const FISH_SPEED_MAX: f32 = 250.;
+/// Fish are drawn between these scales, lightest to heaviest for their
+/// species.
+const FISH_MIN_SCALE: f32 = 2.5;
+const FISH_MAX_SCALE: f32 = 4.;
+/// How far up its species' weight range a fish has to be to be a trophy.
+const TROPHY_FRACTION: f32 = 0.9;
+/// What trophies shimmer with, and what they're called out in.
+pub const TROPHY_COLOR: Color = Color::rgb(1., 0.8, 0.3);
+/// How many times a second trophies shimmer.
+const TROPHY_SHIMMER_RATE: f32 = 1.5;
+/// Fish spawn up to this far either side of the boat, about as far as can
+/// be seen with the camera zoomed all the way out.
+const SPAWN_RADIUS: f32 = 1800.;
+/// Fish this far from the boat are culled, to make room for ones near it.
+const CULL_RADIUS: f32 = 3600.;
+/// How quickly a fish can change its velocity, in px/s².
+const STEERING_FORCE: f32 = 250.;
+/// How far a solitary fish tips up or down while wandering, in radians.
+const MAX_WANDER: f32 = 0.4;
+/// How fast the wander pitch drifts, in radians per second.
+const WANDER_RATE: f32 = 1.5;
+/// Fish tilt to follow their velocity, up to this angle in radians.
+const MAX_PITCH: f32 = 1.;
+/// How near a fish of the same species has to be to count as part of the
+/// school.
+const SCHOOL_RADIUS: f32 = 150.;
+/// Schooling fish keep at least this far apart.
+const SEPARATION_RADIUS: f32 = 30.;
+const SEPARATION_WEIGHT: f32 = 1.5;
+const ALIGNMENT_WEIGHT: f32 = 0.6;
+const COHESION_WEIGHT: f32 = 0.4;
+/// How far from the first fish of a school the rest of it spawns.
+const SCHOOL_SPREAD: f32 = 40.;
+/// Fish stop spawning once this many are alive.
+const MAX_FISH: u32 = 80;
+const FISH_STAMINA_BASE: f32 = 0.5;
+const FISH_STAMINA_PER_ROOT_KG: f32 = 1.5;
 
 pub struct FishPlugin;
 
@@ -178,104 +308,337 @@ impl Plugin for FishPlugin {
             .add_event::<CatchFishEvent>()
             .add_event::<DropFishEvent>()
             .add_event::<ReelingFishEvent>()
+            .add_event::<WeightLimitEvent>()
             .init_resource::<AliveFish>()
+            .insert_resource(FishRegistry::load())
+            .init_resource::<SpatialGrid<Fish>>()
             .add_systems(OnEnter(Game), setup)
+            .add_systems(
+                Update,
+                rebuild_grid::<Fish>
+                    .in_set(GameplaySet::Spatial)
+                    .run_if(in_state(Game)),
+            )
             .add_systems(
                 Update,
                 (
                     update_fish_count,
                     spawn_fish,
+                    follow_depth,
+                    steer_fish,
                     fish_movement,
                     orient_fish,
-                    fish_boundary,
+                    shimmer_trophies,
                     die_the_fish,
                     cull_fish,
                     check_for_rod_collisions,
                     check_for_trash_collisions,
+                    check_for_line_snaps,
                     check_for_boat_collisions,
                     handle_invincibilities,
                 )
+                    .chain()
+                    .in_set(GameplaySet::Fish)
                     .run_if(in_state(Game)),
             );
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn setup(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    registry: Res<FishRegistry>,
+    clock: Res<GameClock>,
+    map: Res<WorldMap>,
+    location: Res<Location>,
     mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+    mut rng: ResMut<GameRng>,
 ) {
+    let rng = rng.stream("fish_spawn");
+    let active: Vec<&FishSpecies> = registry
+        .species
+        .iter()
+        .filter(|species| species.is_active(&clock) && location.0.spawn_weight(species.variant) > 0)
+        .collect();
+    if active.is_empty() {
+        return;
+    }
+
+    let mut spawned = 0;
     for _ in 0..20 {
-        let fish: FishVariant = rand::random();
-        let direction = Direction::random_y();
-
-        let vertical_position = rand::thread_rng().gen_range(fish.get_spawn_depth_range());
-        let horizontal_position = rand::thread_rng().gen_range(-1800.0..1800.);
-
-        commands.spawn({
-            let (texture_atlas, animation_indices) = fish.texture_atlas(asset_server.clone());
-            let texture_atlas_handle = texture_atlases.add(texture_atlas);
-
-            (
-                FishBundle {
-                    sprite_sheet: SpriteSheetBundle {
-                        texture_atlas: texture_atlas_handle,
-                        sprite: TextureAtlasSprite {
-                            index: animation_indices.first,
-                            flip_x: matches!(direction, Direction::Left),
-                            ..default()
-                        },
-                        transform: Transform {
-                            translation: Vec3::new(horizontal_position, -vertical_position, 5.),
-                            scale: Vec3::splat(3.),
-                            ..default()
-                        },
+        let species = active[rng.gen_range(0..active.len())];
+        let x = spawn_position(&map, BOAT_START, rng);
+
+        spawned += spawn_species(
+            &mut commands,
+            &asset_server,
+            &mut texture_atlases,
+            species,
+            x,
+            &clock,
+            location.0.depth,
+            MAX_FISH - spawned,
+            rng,
+        );
+    }
+}
+
+/// Somewhere in the world within reach of the boat at `boat`.
+fn spawn_position<R: Rng + ?Sized>(map: &WorldMap, boat: f32, rng: &mut R) -> f32 {
+    let from = (boat - SPAWN_RADIUS).max(map.start);
+    let to = (boat + SPAWN_RADIUS).min(map.end());
+    rng.gen_range(from..to)
+}
+
+/// Spawn a fish of `species` at `horizontal_position` somewhere in its
+/// depth band, along with as much of its school as there's `room` for.
+/// Returns how many were spawned.
+#[allow(clippy::too_many_arguments)]
+fn spawn_species<R: Rng + ?Sized>(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    texture_atlases: &mut Assets<TextureAtlas>,
+    species: &FishSpecies,
+    horizontal_position: f32,
+    clock: &GameClock,
+    bottom: f32,
+    room: u32,
+    rng: &mut R,
+) -> u32 {
+    let heading = if rng.gen::<bool>() { -1. } else { 1. };
+    let depth = species.depth(clock, bottom);
+    let vertical_position = rng.gen_range(depth.clone());
+    let count = species
+        .school
+        .clone()
+        .map_or(1, |school| rng.gen_range(school))
+        .min(room);
+
+    for i in 0..count {
+        let offset = if i == 0 {
+            Vec2::ZERO
+        } else {
+            Vec2::new(
+                rng.gen_range(-SCHOOL_SPREAD..SCHOOL_SPREAD),
+                rng.gen_range(-SCHOOL_SPREAD..SCHOOL_SPREAD),
+            )
+        };
+        let speed = rng.gen_range(species.speed.clone());
+        let weight = Weight {
+            // Round weight to .2 decimal places
+            current: (rng.gen_range(species.weight.clone()) * 100.0_f32).round() / 100.0,
+        };
+
+        let (texture_atlas, animation_indices) = species.texture_atlas(asset_server);
+        let texture_atlas_handle = texture_atlases.add(texture_atlas);
+
+        let mut fish = commands.spawn((
+            OnGameScreen,
+            FishBundle {
+                marker: Fish,
+                sprite_sheet: SpriteSheetBundle {
+                    texture_atlas: texture_atlas_handle,
+                    sprite: TextureAtlasSprite {
+                        index: animation_indices.first,
+                        flip_x: heading < 0.,
                         ..default()
                     },
-                    direction,
-                    speed: Speed {
-                        current: rand::thread_rng().gen_range(FISH_SPEED_MIN..FISH_SPEED_MAX),
-                    },
-                    variant: fish,
-                    weight: Weight {
-                        // Round weight to .2 decimal places
-                        current: (rand::thread_rng().gen_range(fish.get_weight_range())
-                            * 100.0_f32)
-                            .round()
-                            / 100.0,
+                    transform: Transform {
+                        translation: Vec3::new(
+                            horizontal_position + offset.x,
+                            -vertical_position + offset.y,
+                            5.,
+                        ),
+                        scale: Vec3::splat(species.scale(&weight)),
+                        ..default()
                     },
                     ..default()
                 },
-                animation_indices,
-                AnimationTimer(Timer::from_seconds(0.2, TimerMode::Repeating)),
-            )
-        });
+                velocity: Velocity(Vec3::new(heading * speed, 0., 0.)),
+                steering: FishSteering {
+                    schooling: species.school.is_some(),
+                    depth: depth.clone(),
+                    wander: 0.,
+                },
+                speed: Speed { current: speed },
+                variant: species.variant,
+                hitbox: species.hitbox(),
+                weight,
+                state: FishState::Swimming,
+                can_die: CanDie { dying: false },
+                decay_timer: DecayTimer::random(rng),
+            },
+            animation_indices,
+            AnimationTimer(Timer::from_seconds(0.2, TimerMode::Repeating)),
+        ));
+
+        if species.is_trophy(&weight) {
+            fish.insert(Trophy);
+        }
+    }
+
+    count
+}
+
+/// Turn each swimming fish towards where it wants to go. Every fish wanders
+/// and keeps to its depth band and the world, and schooling fish also flock
+/// with their neighbours: keeping apart, heading the same way and staying
+/// together. Bait on the hook draws in the species it appeals to, and dying
+/// fish swim off away from the boat, to be culled out of sight.
+#[allow(clippy::type_complexity)]
+pub fn steer_fish(
+    time: Res<Time>,
+    mut fish_query: Query<
+        (
+            Entity,
+            &Transform,
+            &mut Velocity,
+            &mut FishSteering,
+            &Speed,
+            &FishVariant,
+            &FishState,
+            &CanDie,
+        ),
+        With<Fish>,
+    >,
+    lure_query: Query<(&Transform, &Lure), (With<Rod>, Without<Fish>)>,
+    player_query: Query<&Transform, (With<Player>, Without<Fish>)>,
+    grid: Res<SpatialGrid<Fish>>,
+    map: Res<WorldMap>,
+    mut rng: ResMut<GameRng>,
+) {
+    let rng = rng.stream("fish_steering");
+    let lure = lure_query.get_single().ok();
+    let boat = player_query.single().translation.x;
+    let delta = time.delta_seconds();
+
+    let swimming: HashMap<Entity, (Vec2, FishVariant)> = fish_query
+        .iter()
+        .filter(|(.., state, _)| **state == FishState::Swimming)
+        .map(|(fish, _, velocity, _, _, variant, ..)| (fish, (velocity.0.truncate(), *variant)))
+        .collect();
+
+    for (fish, transform, mut velocity, mut steering, speed, variant, state, can_die) in
+        &mut fish_query
+    {
+        if *state != FishState::Swimming {
+            continue;
+        }
+
+        let position = transform.translation.truncate();
+        let current = velocity.0.truncate();
+        let heading = if current.x < 0. { -1. } else { 1. };
+
+        steering.wander = (steering.wander + rng.gen_range(-1.0..1.) * WANDER_RATE * delta)
+            .clamp(-MAX_WANDER, MAX_WANDER);
+        let mut desired =
+            Vec2::new(heading * steering.wander.cos(), steering.wander.sin()) * speed.current;
+
+        if steering.schooling {
+            desired += flock(fish, position, *variant, &grid, &swimming) * speed.current;
+        }
+
+        // Depths are measured down from the surface at 0
+        if position.y > -steering.depth.start {
+            desired.y = -speed.current * 0.5;
+        } else if position.y < -steering.depth.end {
+            desired.y = speed.current * 0.5;
+        }
+
+        // Bait a fish likes draws it out of its depth band
+        if let Some((hook, lure)) = lure {
+            let to_hook = hook.translation.truncate() - position;
+            let appeal = lure.appeal(*variant);
+
+            if appeal > 0. && to_hook.length() < lure.radius && !can_die.dying {
+                desired = desired.lerp(to_hook.normalize_or_zero() * speed.current, appeal);
+            }
+        }
+
+        if can_die.dying {
+            desired.x = (position.x - boat).signum() * speed.current;
+        } else if position.x < map.start {
+            desired.x = speed.current;
+        } else if position.x > map.end() {
+            desired.x = -speed.current;
+        }
+
+        let desired = desired.clamp_length_max(speed.current);
+        let steer = (desired - current).clamp_length_max(STEERING_FORCE * delta);
+        velocity.0 = (current + steer).extend(0.);
+    }
+}
+
+/// Move each fish's depth band with the time of day, so the ones that rise
+/// at night swim up to it.
+pub fn follow_depth(
+    clock: Res<GameClock>,
+    registry: Res<FishRegistry>,
+    location: Res<Location>,
+    mut fish_query: Query<(&mut FishSteering, &FishVariant), With<Fish>>,
+) {
+    for (mut steering, variant) in &mut fish_query {
+        let depth = registry.get(*variant).depth(&clock, location.0.depth);
+        if steering.depth != depth {
+            steering.depth = depth;
+        }
+    }
+}
+
+/// Separation, alignment and cohesion with the rest of the school, each as a
+/// fraction of the fish's speed.
+fn flock(
+    fish: Entity,
+    position: Vec2,
+    variant: FishVariant,
+    grid: &SpatialGrid<Fish>,
+    swimming: &HashMap<Entity, (Vec2, FishVariant)>,
+) -> Vec2 {
+    let mut separation = Vec2::ZERO;
+    let mut total_velocity = Vec2::ZERO;
+    let mut total_position = Vec2::ZERO;
+    let mut neighbours = 0;
+
+    for (other, other_position) in grid.within(position, SCHOOL_RADIUS) {
+        let Some((other_velocity, other_variant)) = swimming.get(&other) else {
+            continue;
+        };
+        if other == fish || *other_variant != variant {
+            continue;
+        }
+
+        let distance = position.distance(other_position);
+        if distance < SEPARATION_RADIUS {
+            separation += (position - other_position).normalize_or_zero()
+                * (1. - distance / SEPARATION_RADIUS);
+        }
+        total_velocity += *other_velocity;
+        total_position += other_position;
+        neighbours += 1;
+    }
+
+    if neighbours == 0 {
+        return Vec2::ZERO;
     }
+
+    let alignment = total_velocity.normalize_or_zero();
+    let cohesion = (total_position / neighbours as f32 - position) / SCHOOL_RADIUS;
+
+    separation * SEPARATION_WEIGHT + alignment * ALIGNMENT_WEIGHT + cohesion * COHESION_WEIGHT
 }
 
 pub fn fish_movement(
     time: Res<Time>,
     rod_query: Query<&Transform, (With<Rod>, Without<Fish>)>,
-    mut fish_query: Query<(&mut Transform, &mut Direction, &Speed, &FishState), With<Fish>>,
+    mut fish_query: Query<(&mut Transform, &Velocity, &FishState), With<Fish>>,
 ) {
-    for (mut transform, direction, speed, state) in &mut fish_query {
+    for (mut transform, velocity, state) in &mut fish_query {
         match state {
             FishState::Swimming => {
-                // Move the thing
-                match *direction {
-                    Direction::Left => {
-                        transform.translation.x -= 1.0 * time.delta_seconds() * speed.current
-                    }
-                    Direction::Right => {
-                        transform.translation.x += 1.0 * time.delta_seconds() * speed.current
-                    }
-                    Direction::Down => {
-                        transform.translation.y -= 0.5 * time.delta_seconds() * speed.current
-                    }
-                    _ => {}
-                }
+                transform.translation += velocity.0 * time.delta_seconds();
             }
-            FishState::Caught => {
+            FishState::Hooked | FishState::Caught => {
                 if let Ok(rod) = rod_query.get_single() {
                     transform.translation.x = rod.translation.x;
                     transform.translation.y = rod.translation.y;
@@ -285,53 +648,39 @@ pub fn fish_movement(
     }
 }
 
-pub fn fish_boundary(
-    mut fish_query: Query<(&mut Transform, &FishState, &mut Direction, &CanDie), With<Fish>>,
+/// Face each fish the way it's swimming, tilted up or down with its course.
+pub fn orient_fish(
+    mut fish_query: Query<(&mut TextureAtlasSprite, &mut Transform, &Velocity), With<Fish>>,
 ) {
-    for (transform, _fish_state, mut direction, can_die) in &mut fish_query {
-        if transform.translation.y > -100. && *direction != Direction::Down {
-            *direction = Direction::Down
+    for (mut fish, mut transform, velocity) in &mut fish_query {
+        if velocity.0.x == 0. && velocity.0.y == 0. {
+            continue;
         }
 
-        if transform.translation.y < -100. && *direction == Direction::Down {
-            *direction = Direction::random_y();
-        }
+        let pitch = velocity
+            .0
+            .y
+            .atan2(velocity.0.x.abs())
+            .clamp(-MAX_PITCH, MAX_PITCH);
 
-        if can_die.dying {
-            return;
-        }
-
-        // Flip the thing when at edge
-        if transform.translation.x < -1800. {
-            *direction = Direction::Right;
-        } else if transform.translation.x > 1800. {
-            *direction = Direction::Left;
-        }
+        fish.flip_x = velocity.0.x < 0.;
+        // The sprite faces right, so a flipped fish tilts the other way
+        transform.rotation = Quat::from_rotation_z(if fish.flip_x { -pitch } else { pitch });
     }
 }
 
-pub fn orient_fish(
-    mut fish_query: Query<(&mut TextureAtlasSprite, &mut Transform, &mut Direction), With<Fish>>,
+/// Pulse trophies between their own colours and gold, so they stand out.
+pub fn shimmer_trophies(
+    time: Res<Time>,
+    mut trophy_query: Query<&mut TextureAtlasSprite, With<Trophy>>,
 ) {
-    for (mut fish, mut transform, direction) in &mut fish_query {
-        match *direction {
-            Direction::Left => {
-                fish.flip_x = true;
-                transform.rotation = Quat::IDENTITY;
-            }
-            Direction::Right => {
-                fish.flip_x = false;
-                transform.rotation = Quat::IDENTITY;
-            }
-            Direction::Up => {
-                fish.flip_x = false;
-                transform.rotation = Quat::from_rotation_z(std::f32::consts::PI / 2.0);
-            }
-            Direction::Down => {
-                fish.flip_x = false;
-                transform.rotation = Quat::from_rotation_z(-std::f32::consts::PI / 2.0);
-            }
-        }
+    let t =
+        (time.elapsed_seconds() * TROPHY_SHIMMER_RATE * std::f32::consts::TAU).sin() * 0.5 + 0.5;
+    let [r, g, b, _] = TROPHY_COLOR.as_rgba_f32();
+    let color = Color::rgb(1. + (r - 1.) * t, 1. + (g - 1.) * t, 1. + (b - 1.) * t);
+
+    for mut sprite in &mut trophy_query {
+        sprite.color = color;
     }
 }
 
@@ -343,19 +692,24 @@ pub fn die_the_fish(mut fishicide_query: Query<(&DecayTimer, &mut CanDie), With<
     }
 }
 
+/// Cull fish that have swum off out of sight to die, and any the boat has
+/// left far behind.
 pub fn cull_fish(
     mut commands: Commands,
     mut fish_query: Query<(Entity, &CanDie, &Transform), With<Fish>>,
+    player_query: Query<&Transform, (With<Player>, Without<Fish>)>,
 ) {
+    let boat = player_query.single().translation.x;
+
     for (fish, can_die, &transform) in &mut fish_query {
-        let fish_out_of_bounds =
-            transform.translation.x > 1800. || transform.translation.x < -1800.;
-        if can_die.dying && fish_out_of_bounds {
+        let distance = (transform.translation.x - boat).abs();
+        if (can_die.dying && distance > SPAWN_RADIUS) || distance > CULL_RADIUS {
             commands.entity(fish).despawn();
         }
     }
 }
 
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 pub fn check_for_boat_collisions(
     mut commands: Commands,
     mut ev_boat_collision: EventReader<BoatCollisionEvent>,
@@ -364,27 +718,48 @@ pub fn check_for_boat_collisions(
     mut ev_weight_limit: EventWriter<WeightLimitEvent>,
     mut player_query: Query<&mut FishStorage, With<Player>>,
     mut fish_stored: ResMut<PlayerFishStored>,
-    mut fish_query: Query<(Entity, &mut FishState, &FishVariant, &Weight), With<Fish>>,
+    mut fish_query: Query<
+        (
+            Entity,
+            &mut FishState,
+            &FishVariant,
+            &Weight,
+            Option<&HookDepth>,
+            Has<Trophy>,
+        ),
+        With<Fish>,
+    >,
 ) {
     let mut fish_storage = player_query.single_mut();
 
     for _ in ev_boat_collision.read() {
-        for (fish, mut state, fish_variant, weight) in &mut fish_query {
+        for (fish, mut state, fish_variant, weight, depth, trophy) in &mut fish_query {
             match *state {
-                FishState::Swimming => {}
+                // A fish still fighting isn't landed just for being
+                // dragged up to the hull
+                FishState::Swimming | FishState::Hooked => {}
                 FishState::Caught => {
                     if (weight.current + fish_storage.current) > fish_storage.max {
                         *state = FishState::Swimming;
+                        commands.entity(fish).remove::<FishStamina>();
 
                         ev_drop_fish.send_default();
                         ev_weight_limit.send_default();
                     } else {
+                        let depth = depth.map_or(0., |depth| depth.0);
                         fish_storage.current += weight.current;
-                        fish_stored.fish.push((*fish_variant, *weight));
+                        fish_stored.fish.push(StoredFish {
+                            variant: *fish_variant,
+                            weight: *weight,
+                            depth,
+                            freshness: 1.,
+                        });
                         commands.entity(fish).despawn();
                         ev_catch_fish.send(CatchFishEvent {
                             weight: *weight,
                             fish_variant: *fish_variant,
+                            trophy,
+                            depth,
                         });
                     }
                 }
@@ -393,16 +768,18 @@ pub fn check_for_boat_collisions(
     }
 }
 
+#[allow(clippy::type_complexity)]
 pub fn check_for_rod_collisions(
+    mut commands: Commands,
     mut ev_fish_collision_with_rod: EventReader<FishCollisionWithRodEvent>,
     mut ev_reeling_fish: EventWriter<ReelingFishEvent>,
     mut fish_query: Query<
-        (Entity, &mut FishState, &FishVariant, &Weight),
+        (Entity, &mut FishState, &FishVariant, &Weight, &Transform),
         (With<Fish>, Without<Invincibility>),
     >,
 ) {
     for ev in ev_fish_collision_with_rod.read() {
-        for (fish, mut state, fish_variant, weight) in &mut fish_query {
+        for (fish, mut state, fish_variant, weight, transform) in &mut fish_query {
             if fish != ev.fish {
                 continue;
             }
@@ -411,11 +788,16 @@ pub fn check_for_rod_collisions(
                 fish_variant: *fish_variant,
             });
 
-            *state = FishState::Caught
+            *state = FishState::Hooked;
+            commands.entity(fish).insert((
+                FishStamina::from_weight(weight),
+                HookDepth(-transform.translation.y),
+            ));
         }
     }
 }
 
+#[allow(clippy::type_complexity)]
 pub fn check_for_trash_collisions(
     mut commands: Commands,
     mut ev_trash_collision: EventReader<TrashCollisionEvent>,
@@ -424,26 +806,53 @@ pub fn check_for_trash_collisions(
 ) {
     for _ in ev_trash_collision.read() {
         for (fish, mut state) in &mut fish_query {
-            match *state {
-                FishState::Swimming => {}
-                FishState::Caught => {
-                    commands.entity(fish).insert(Invincibility {
-                        invincibility_timer: Timer::from_seconds(
-                            FISH_INVINCIBILITY_TIME,
-                            TimerMode::Once,
-                        ),
-                    });
-                    *state = FishState::Swimming;
+            release_fish(&mut commands, fish, &mut state, camera_query.single(), 0.8);
+        }
+    }
+}
 
-                    let (camera_entity, camera_transform) = camera_query.single();
+#[allow(clippy::type_complexity)]
+pub fn check_for_line_snaps(
+    mut commands: Commands,
+    mut ev_line_snap: EventReader<LineSnapEvent>,
+    mut fish_query: Query<(Entity, &mut FishState), With<Fish>>,
+    camera_query: Query<(Entity, &Transform), (Without<Fish>, With<Camera2d>)>,
+) {
+    for _ in ev_line_snap.read() {
+        for (fish, mut state) in &mut fish_query {
+            release_fish(&mut commands, fish, &mut state, camera_query.single(), 1.2);
+        }
+    }
+}
 
-                    commands.entity(camera_entity).insert(CameraShake {
-                        shake_timer: Timer::from_seconds(0.1, TimerMode::Once),
-                        intensity: 0.8,
-                        start_translation: camera_transform.translation,
-                    });
-                }
-            }
+/// Let a fish on the line swim free, briefly ignoring the hook so it can
+/// get away.
+fn release_fish(
+    commands: &mut Commands,
+    fish: Entity,
+    state: &mut FishState,
+    (camera_entity, camera_transform): (Entity, &Transform),
+    shake_intensity: f32,
+) {
+    match *state {
+        FishState::Swimming => {}
+        FishState::Hooked | FishState::Caught => {
+            commands
+                .entity(fish)
+                .insert(Invincibility {
+                    invincibility_timer: Timer::from_seconds(
+                        FISH_INVINCIBILITY_TIME,
+                        TimerMode::Once,
+                    ),
+                })
+                .remove::<FishStamina>();
+            *state = FishState::Swimming;
+
+            commands.entity(camera_entity).insert(CameraShake {
+                shake_timer: Timer::from_seconds(0.1, TimerMode::Once),
+                intensity: shake_intensity,
+                start_translation: camera_transform.translation,
+            });
         }
     }
 }
@@ -460,76 +869,46 @@ fn handle_invincibilities(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn spawn_fish(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     alive_fish: Res<AliveFish>,
+    registry: Res<FishRegistry>,
+    clock: Res<GameClock>,
+    (weather, weathers): (Res<Weather>, Res<WeatherCatalog>),
+    (map, location): (Res<WorldMap>, Res<Location>),
+    player_query: Query<&Transform, With<Player>>,
     mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+    mut rng: ResMut<GameRng>,
 ) {
-    if alive_fish.count > 80 {
+    if alive_fish.count >= MAX_FISH {
         return;
     }
 
-    let fish_weights = [100, 70, 55, 40, 30, 2, 15, 9];
-    let dist = WeightedIndex::new(fish_weights).unwrap();
-
-    let mut rng = rand::thread_rng();
-
-    let fish_index: usize = dist.sample(&mut rng);
-    let fish = match fish_index {
-        0 => FishVariant::One,
-        1 => FishVariant::Two,
-        2 => FishVariant::Three,
-        3 => FishVariant::Four,
-        4 => FishVariant::Five,
-        5 => FishVariant::Six,
-        6 => FishVariant::Seven,
-        7 => FishVariant::Eight,
-        _ => panic!("Invalid fish variant index"), // This should not happen
+    let rng = rng.stream("fish_spawn");
+    let x = spawn_position(&map, player_query.single().translation.x, rng);
+    let Some(species) = registry.weighted_random(
+        &clock,
+        &location.0,
+        weathers.get(weather.kind),
+        map.ground_at(x),
+        rng,
+    ) else {
+        return;
     };
 
-    let direction = Direction::random_y();
-
-    let vertical_position = rand::thread_rng().gen_range(fish.get_spawn_depth_range());
-    let horizontal_position = rand::thread_rng().gen_range(-1800.0..1800.);
-
-    commands.spawn({
-        let (texture_atlas, animation_indices) = fish.texture_atlas(asset_server.clone());
-        let texture_atlas_handle = texture_atlases.add(texture_atlas);
-
-        (
-            FishBundle {
-                sprite_sheet: SpriteSheetBundle {
-                    texture_atlas: texture_atlas_handle,
-                    sprite: TextureAtlasSprite {
-                        index: animation_indices.first,
-                        flip_x: matches!(direction, Direction::Left),
-                        ..default()
-                    },
-                    transform: Transform {
-                        translation: Vec3::new(horizontal_position, -vertical_position, 5.),
-                        scale: Vec3::splat(3.),
-                        ..default()
-                    },
-                    ..default()
-                },
-                direction,
-                speed: Speed {
-                    current: rand::thread_rng().gen_range(FISH_SPEED_MIN..FISH_SPEED_MAX),
-                },
-                variant: fish,
-                weight: Weight {
-                    // Round weight to .2 decimal places
-                    current: (rand::thread_rng().gen_range(fish.get_weight_range()) * 100.0_f32)
-                        .round()
-                        / 100.0,
-                },
-                ..default()
-            },
-            animation_indices,
-            AnimationTimer(Timer::from_seconds(0.2, TimerMode::Repeating)),
-        )
-    });
+    spawn_species(
+        &mut commands,
+        &asset_server,
+        &mut texture_atlases,
+        species,
+        x,
+        &clock,
+        location.0.depth,
+        MAX_FISH - alive_fish.count,
+        rng,
+    );
 }
 
 pub fn update_fish_count(fish_query: Query<&Fish>, mut alive_fish: ResMut<AliveFish>) {
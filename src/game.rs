@@ -1,30 +1,134 @@
 use bevy::prelude::*;
 
-use super::{despawn_screen, GameState};
+use super::{configure_gameplay_sets, despawn_screen, GameState, PauseState};
 use crate::{
-    fish::FishPlugin, player::PlayerPlugin, port::PortPlugin, rod::RodPlugin,
-    speech::PlayerTextPlugin, trash::TrashPlugin,
+    achievements::AchievementsPlugin,
+    bait::{BaitInventory, BaitPlugin},
+    clock::{ClockPlugin, GameClock},
+    contracts::{ContractBoard, ContractsPlugin},
+    events::ResumeEvent,
+    fish::FishPlugin,
+    journal::{FishJournal, JournalPlugin},
+    location::{set_location, LocationKind, LocationPlugin},
+    player::PlayerPlugin,
+    port::PortPlugin,
+    records::RecordsPlugin,
+    replay::Replay,
+    resources::{AliveFish, GameRng, PlayerFishStored, PortStorage},
+    rod::RodPlugin,
+    save::{LoadedSave, SavePlugin},
+    shop::ShopPlugin,
+    speech::PlayerTextPlugin,
+    spoilage::SpoilagePlugin,
+    systems::{pause_the_game, pause_time, restart_game, resume_action, resume_game, unpause_time},
+    trash::TrashPlugin,
+    weather::{Weather, WeatherPlugin},
+    world::WorldPlugin,
 };
 
+/// Height of the water line, where the waves sit.
+pub const WATER_SURFACE: f32 = -46.;
+
 pub struct GamePlugin;
 
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(GameState::Game), game_setup)
-            .add_plugins((
+        configure_gameplay_sets(app);
+
+        app.add_plugins((
+            (
                 PlayerPlugin,
                 RodPlugin,
                 FishPlugin,
                 PortPlugin,
                 TrashPlugin,
                 PlayerTextPlugin,
-            ))
-            .add_systems(OnExit(GameState::Game), despawn_screen::<OnGameScreen>);
+                SavePlugin,
+                ShopPlugin,
+                BaitPlugin,
+                ClockPlugin,
+                WeatherPlugin,
+                WorldPlugin,
+                LocationPlugin,
+                ContractsPlugin,
+                SpoilagePlugin,
+            ),
+            // Progress kept across the session
+            (JournalPlugin, RecordsPlugin, AchievementsPlugin),
+        ))
+        .add_systems(
+            OnExit(GameState::Game),
+            (
+                despawn_screen::<OnGameScreen>,
+                // The state has already moved on by the time this runs
+                reset_session.run_if(not(in_state(GameState::Travelling))),
+            ),
+        )
+        .add_event::<ResumeEvent>()
+        .add_systems(
+            Update,
+            (
+                pause_the_game.run_if(in_state(PauseState::Running)),
+                // A replay resumes when its recording did, since which
+                // menu page was open isn't recorded
+                resume_action
+                    .run_if(in_state(PauseState::Paused))
+                    .run_if(not(resource_exists::<Replay>())),
+                resume_game,
+            )
+                .chain()
+                .run_if(in_state(GameState::Game)),
+        )
+        .add_systems(OnEnter(PauseState::Paused), pause_time)
+        .add_systems(OnExit(PauseState::Paused), unpause_time)
+        .add_systems(OnEnter(GameState::Restarting), restart_game);
     }
 }
 
-// Tag component used to tag entities added on the game screen
+/// Tags every entity that belongs to a session, so they are all despawned
+/// when it ends. Children go with their parents, so only tag the root.
 #[derive(Component)]
-struct OnGameScreen;
+pub struct OnGameScreen;
+
+/// Put back every resource a session changes, so the next one starts clean.
+/// A save being continued is applied over these before the session starts.
+/// Records span every game played, so they're left alone.
+fn reset_session(mut commands: Commands, mut rng: ResMut<GameRng>) {
+    commands.insert_resource(PortStorage::default());
+    commands.insert_resource(PlayerFishStored::default());
+    commands.insert_resource(AliveFish::default());
+    commands.insert_resource(BaitInventory::default());
+    commands.insert_resource(GameClock::default());
+    commands.insert_resource(FishJournal::default());
+    commands.insert_resource(ContractBoard::default());
+    commands.insert_resource(Weather::default());
+    set_location(&mut commands, LocationKind::default());
+    commands.remove_resource::<LoadedSave>();
+    rng.restart();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fish::FishVariant, location::LocationCatalog, records::Records};
+    use bevy::ecs::system::RunSystemOnce;
 
-fn game_setup() {}
+    #[test]
+    fn records_outlive_the_session() {
+        let mut world = World::new();
+        world.insert_resource(LocationCatalog::load());
+        world.insert_resource(GameRng::new(1));
+        world.insert_resource(Records {
+            heaviest: [(FishVariant::Six, 420.)].into(),
+            largest_haul: 90.,
+            deepest: 800.,
+        });
+
+        world.run_system_once(reset_session);
+
+        let records = world.resource::<Records>();
+        assert_eq!(records.heaviest.get(&FishVariant::Six), Some(&420.));
+        assert_eq!(records.largest_haul, 90.);
+        assert_eq!(records.deepest, 800.);
+    }
+}
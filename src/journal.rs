@@ -0,0 +1,335 @@
+use crate::{
+    clock::{format_hour, GameClock},
+    despawn_screen,
+    events::CatchFishEvent,
+    fish::{FishRegistry, FishSpecies, FishVariant},
+    menu::{MenuState, NORMAL_BUTTON},
+    resources::PortStorage,
+    GameState::Game,
+    GameplaySet, TEXT_COLOR,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Largest a species' sprite is drawn in the journal, in px.
+const SPRITE_BOX: Vec2 = Vec2::new(100., 70.);
+const CARD_COLOR: Color = Color::rgba(1., 1., 1., 0.06);
+const UNCAUGHT_TEXT_COLOR: Color = Color::rgb(0.5, 0.5, 0.5);
+
+/// When the first of a species was landed.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct CatchDate {
+    pub day: u32,
+    pub hour: f32,
+}
+
+impl CatchDate {
+    pub fn describe(&self) -> String {
+        format!("day {} at {}", self.day, format_hour(self.hour))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct JournalEntry {
+    pub caught: u32,
+    /// In kg.
+    pub heaviest: f32,
+    pub first_caught: CatchDate,
+}
+
+/// Every species the player has landed, kept with the save.
+#[derive(Resource, Serialize, Deserialize, Default, Debug, Clone)]
+pub struct FishJournal {
+    pub entries: HashMap<FishVariant, JournalEntry>,
+}
+
+impl FishJournal {
+    pub fn get(&self, variant: FishVariant) -> Option<&JournalEntry> {
+        self.entries.get(&variant)
+    }
+}
+
+/// The menu page to go back to once the journal is closed.
+#[derive(Resource)]
+pub struct JournalReturn(pub MenuState);
+
+/// Bring up the journal over the `from` page.
+pub fn open_journal(
+    commands: &mut Commands,
+    menu_state: &mut NextState<MenuState>,
+    from: MenuState,
+) {
+    commands.insert_resource(JournalReturn(from));
+    menu_state.set(MenuState::Journal);
+}
+
+#[derive(Component)]
+struct OnJournalScreen;
+
+#[derive(Component)]
+struct JournalBackButton;
+
+pub struct JournalPlugin;
+
+impl Plugin for JournalPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FishJournal>()
+            .add_systems(
+                Update,
+                record_catches
+                    .after(GameplaySet::Fish)
+                    .run_if(in_state(Game)),
+            )
+            .add_systems(OnEnter(MenuState::Journal), journal_setup)
+            .add_systems(
+                OnExit(MenuState::Journal),
+                despawn_screen::<OnJournalScreen>,
+            )
+            .add_systems(Update, journal_action.run_if(in_state(MenuState::Journal)));
+    }
+}
+
+fn record_catches(
+    mut ev_catch_fish: EventReader<CatchFishEvent>,
+    clock: Res<GameClock>,
+    mut journal: ResMut<FishJournal>,
+) {
+    for ev in ev_catch_fish.read() {
+        let weight = ev.weight.current;
+        let entry = journal
+            .entries
+            .entry(ev.fish_variant)
+            .or_insert(JournalEntry {
+                caught: 0,
+                heaviest: weight,
+                first_caught: CatchDate {
+                    day: clock.day,
+                    hour: clock.hour,
+                },
+            });
+
+        entry.caught += 1;
+        entry.heaviest = entry.heaviest.max(weight);
+    }
+}
+
+fn depth_text(species: &FishSpecies) -> String {
+    let day = &species.depth;
+    match &species.night_depth {
+        Some(night) => format!(
+            "Swims {:.0}-{:.0} down, {:.0}-{:.0} at night",
+            day.start, day.end, night.start, night.end
+        ),
+        None => format!("Swims {:.0}-{:.0} down", day.start, day.end),
+    }
+}
+
+fn journal_setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+    registry: Res<FishRegistry>,
+    journal: Res<FishJournal>,
+    port: Res<PortStorage>,
+) {
+    let text_style = TextStyle {
+        font: asset_server.load("fonts/Pixellari.ttf"),
+        font_size: 24.0,
+        color: TEXT_COLOR,
+    };
+    let detail_style = TextStyle {
+        font_size: 16.0,
+        ..text_style.clone()
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.85).into(),
+                ..default()
+            },
+            OnJournalScreen,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Fish Journal",
+                TextStyle {
+                    font_size: 50.0,
+                    ..text_style.clone()
+                },
+            ));
+            parent.spawn(
+                TextBundle::from_section(
+                    format!(
+                        "{} of {} species caught",
+                        journal.entries.len(),
+                        registry.species.len()
+                    ),
+                    detail_style.clone(),
+                )
+                .with_style(Style {
+                    margin: UiRect::bottom(Val::Px(10.0)),
+                    ..default()
+                }),
+            );
+
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Px(1200.0),
+                        flex_wrap: FlexWrap::Wrap,
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for species in &registry.species {
+                        let entry = journal.get(species.variant);
+                        let sold = port.fish.get(&species.variant).copied().unwrap_or(0);
+
+                        let (texture_atlas, animation_indices) =
+                            species.texture_atlas(&asset_server);
+                        let (width, height) = species.frame_size;
+                        let scale = (SPRITE_BOX.x / width).min(SPRITE_BOX.y / height);
+                        // Uncaught species are only a silhouette
+                        let tint = if entry.is_some() {
+                            Color::WHITE
+                        } else {
+                            Color::BLACK
+                        };
+
+                        let (name, description, stats, color) = match entry {
+                            Some(entry) => (
+                                species.name.clone(),
+                                species.description.clone(),
+                                format!(
+                                    "Caught {}, sold {}. Heaviest {:.2} kg. First caught on {}",
+                                    entry.caught,
+                                    sold,
+                                    entry.heaviest,
+                                    entry.first_caught.describe()
+                                ),
+                                TEXT_COLOR,
+                            ),
+                            None => (
+                                "???".to_string(),
+                                "Not caught yet".to_string(),
+                                String::new(),
+                                UNCAUGHT_TEXT_COLOR,
+                            ),
+                        };
+
+                        parent
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    width: Val::Px(585.0),
+                                    height: Val::Px(115.0),
+                                    margin: UiRect::all(Val::Px(5.0)),
+                                    padding: UiRect::all(Val::Px(5.0)),
+                                    align_items: AlignItems::Center,
+                                    ..default()
+                                },
+                                background_color: CARD_COLOR.into(),
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                parent
+                                    .spawn(NodeBundle {
+                                        style: Style {
+                                            width: Val::Px(SPRITE_BOX.x + 10.),
+                                            justify_content: JustifyContent::Center,
+                                            flex_shrink: 0.,
+                                            ..default()
+                                        },
+                                        ..default()
+                                    })
+                                    .with_children(|parent| {
+                                        parent.spawn(AtlasImageBundle {
+                                            style: Style {
+                                                width: Val::Px(width * scale),
+                                                height: Val::Px(height * scale),
+                                                ..default()
+                                            },
+                                            texture_atlas: texture_atlases.add(texture_atlas),
+                                            texture_atlas_image: UiTextureAtlasImage {
+                                                index: animation_indices.first,
+                                                ..default()
+                                            },
+                                            background_color: tint.into(),
+                                            ..default()
+                                        });
+                                    });
+
+                                parent
+                                    .spawn(NodeBundle {
+                                        style: Style {
+                                            flex_direction: FlexDirection::Column,
+                                            ..default()
+                                        },
+                                        ..default()
+                                    })
+                                    .with_children(|parent| {
+                                        parent.spawn(TextBundle::from_section(
+                                            name,
+                                            TextStyle {
+                                                color,
+                                                ..text_style.clone()
+                                            },
+                                        ));
+                                        for line in [description, depth_text(species), stats] {
+                                            parent.spawn(TextBundle::from_section(
+                                                line,
+                                                TextStyle {
+                                                    color,
+                                                    ..detail_style.clone()
+                                                },
+                                            ));
+                                        }
+                                    });
+                            });
+                    }
+                });
+
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: Style {
+                            width: Val::Px(200.0),
+                            height: Val::Px(50.0),
+                            margin: UiRect::all(Val::Px(10.0)),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        background_color: NORMAL_BUTTON.into(),
+                        ..default()
+                    },
+                    JournalBackButton,
+                ))
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section("Back", text_style));
+                });
+        });
+}
+
+fn journal_action(
+    back_query: Query<&Interaction, (Changed<Interaction>, With<JournalBackButton>)>,
+    from: Option<Res<JournalReturn>>,
+    mut menu_state: ResMut<NextState<MenuState>>,
+) {
+    for interaction in &back_query {
+        if *interaction == Interaction::Pressed {
+            menu_state.set(from.as_ref().map_or(MenuState::Pause, |from| from.0));
+        }
+    }
+}
@@ -0,0 +1,106 @@
+pub mod achievements;
+pub mod actions;
+#[cfg(feature = "audio")]
+pub mod audio;
+pub mod bait;
+pub mod camera;
+pub mod clock;
+pub mod components;
+pub mod contracts;
+pub mod debug;
+pub mod events;
+pub mod fish;
+pub mod game;
+pub mod journal;
+pub mod location;
+pub mod menu;
+pub mod player;
+pub mod port;
+pub mod records;
+pub mod replay;
+pub mod resources;
+pub mod rod;
+pub mod save;
+pub mod shop;
+pub mod spatial;
+pub mod speech;
+pub mod spoilage;
+pub mod systems;
+pub mod trash;
+pub mod ui;
+pub mod weather;
+pub mod world;
+
+use bevy::prelude::*;
+
+pub const TEXT_COLOR: Color = Color::rgb(0.9, 0.9, 0.9);
+
+#[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Hash, States)]
+pub enum GameState {
+    #[default]
+    Menu,
+    Game,
+    /// Passed through on the way back into `Game`, so a restart runs the
+    /// same `OnExit` and `OnEnter` schedules as quitting and starting over.
+    Restarting,
+    /// Passed through on the way to another location. Everything is spawned
+    /// again there, but the session carries on.
+    Travelling,
+}
+
+/// Whether gameplay is running or frozen behind the pause menu.
+#[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Hash, States)]
+pub enum PauseState {
+    #[default]
+    Running,
+    Paused,
+}
+
+/// Gameplay systems run in these sets, one after another, and are chained
+/// inside each set too. Leaving nothing for the scheduler to decide means a
+/// session plays out the same way every time it is replayed.
+#[derive(SystemSet, Clone, Copy, Eq, PartialEq, Debug, Hash)]
+pub enum GameplaySet {
+    Clock,
+    Weather,
+    Player,
+    /// Rebuilding the spatial grids, once the player has moved and before
+    /// anything collides.
+    Spatial,
+    Rod,
+    Bait,
+    Fish,
+    Trash,
+    Port,
+    Shop,
+    Camera,
+    Speech,
+}
+
+pub fn configure_gameplay_sets(app: &mut App) {
+    app.add_state::<PauseState>().configure_sets(
+        Update,
+        (
+            GameplaySet::Clock,
+            GameplaySet::Weather,
+            GameplaySet::Player,
+            GameplaySet::Spatial,
+            GameplaySet::Rod,
+            GameplaySet::Bait,
+            GameplaySet::Fish,
+            GameplaySet::Trash,
+            GameplaySet::Port,
+            GameplaySet::Shop,
+            GameplaySet::Camera,
+            GameplaySet::Speech,
+        )
+            .chain()
+            .run_if(in_state(PauseState::Running)),
+    );
+}
+
+pub fn despawn_screen<T: Component>(to_despawn: Query<Entity, With<T>>, mut commands: Commands) {
+    for entity in &to_despawn {
+        commands.entity(entity).despawn_recursive();
+    }
+}
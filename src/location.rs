@@ -0,0 +1,400 @@
+use crate::{
+    actions::GameAction,
+    despawn_screen,
+    events::{PortCollisionEvent, ResumeEvent, TravelEvent},
+    fish::FishVariant,
+    journal::open_journal,
+    menu::{MenuState, NORMAL_BUTTON},
+    replay::Replay,
+    resources::{load_data_file, PortStorage},
+    save::{LoadedSave, SaveData, Session},
+    trash::TrashVariant,
+    world::WorldMap,
+    GameState, GameplaySet, PauseState, TEXT_COLOR,
+};
+use bevy::{prelude::*, utils::HashMap};
+use serde::{Deserialize, Serialize};
+
+const LOCKED_TEXT_COLOR: Color = Color::rgb(0.5, 0.5, 0.5);
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum LocationKind {
+    River,
+    Lake,
+    #[default]
+    Reef,
+    Ocean,
+    FrozenLake,
+}
+
+/// Somewhere to fish, loaded from `assets/data/locations.ron`.
+#[derive(Deserialize, Debug, Clone)]
+pub struct LocationSpec {
+    pub kind: LocationKind,
+    pub name: String,
+    pub description: String,
+    /// Kg of fish that have to be sold at the port before it can be
+    /// travelled to.
+    pub unlock: f32,
+    /// Multiplies the colour of the sky.
+    pub sky: (f32, f32, f32),
+    /// Multiplies the colour of the water.
+    pub water: (f32, f32, f32),
+    /// Sprites dotted along the water line, in turn.
+    #[serde(default)]
+    pub scenery: Vec<String>,
+    /// Image standing on the horizon, in front of the sky.
+    #[serde(default)]
+    pub backdrop: Option<String>,
+    /// How far down the bottom is, in px. Nothing swims or sinks below it.
+    pub depth: f32,
+    pub music: String,
+    /// Spawn weight of every species found here. Any left out aren't.
+    pub species: HashMap<FishVariant, u32>,
+    /// Spawn weight of every kind of rubbish found here.
+    #[serde(default)]
+    pub trash: HashMap<TrashVariant, u32>,
+    pub world: WorldMap,
+}
+
+impl LocationSpec {
+    pub fn spawn_weight(&self, variant: FishVariant) -> u32 {
+        self.species.get(&variant).copied().unwrap_or(0)
+    }
+
+    pub fn trash_weight(&self, variant: TrashVariant) -> u32 {
+        self.trash.get(&variant).copied().unwrap_or(0)
+    }
+
+    pub fn is_unlocked(&self, port: &PortStorage) -> bool {
+        port.weight >= self.unlock
+    }
+
+    pub fn sky_tint(&self) -> Vec4 {
+        Vec4::new(self.sky.0, self.sky.1, self.sky.2, 1.)
+    }
+
+    pub fn water_tint(&self) -> Vec4 {
+        Vec4::new(self.water.0, self.water.1, self.water.2, 1.)
+    }
+}
+
+#[derive(Resource, Deserialize, Debug)]
+pub struct LocationCatalog {
+    pub locations: Vec<LocationSpec>,
+}
+
+impl LocationCatalog {
+    pub const PATH: &'static str = "data/locations.ron";
+
+    pub fn load() -> Self {
+        load_data_file(Self::PATH)
+    }
+
+    pub fn get(&self, kind: LocationKind) -> &LocationSpec {
+        self.locations
+            .iter()
+            .find(|location| location.kind == kind)
+            .expect("every location kind to have a catalog entry")
+    }
+}
+
+/// Where the session is being fished. Set through [`set_location`], which
+/// lays out the [`WorldMap`] to match.
+#[derive(Resource, Debug)]
+pub struct Location(pub LocationSpec);
+
+/// Move the session to `kind`, along with the layout of the world there.
+pub fn set_location(commands: &mut Commands, kind: LocationKind) {
+    commands.add(move |world: &mut World| {
+        let spec = world.resource::<LocationCatalog>().get(kind).clone();
+        world.insert_resource(spec.world.clone());
+        world.insert_resource(Location(spec));
+    });
+}
+
+#[derive(Component)]
+struct OnMapScreen;
+
+/// Travels to the location of this kind.
+#[derive(Component)]
+struct TravelButton(LocationKind);
+
+#[derive(Component)]
+struct MapBackButton;
+
+#[derive(Component)]
+struct MapJournalButton;
+
+pub struct LocationPlugin;
+
+impl Plugin for LocationPlugin {
+    fn build(&self, app: &mut App) {
+        let catalog = LocationCatalog::load();
+        let spec = catalog.get(LocationKind::default()).clone();
+
+        app.add_event::<TravelEvent>()
+            .insert_resource(spec.world.clone())
+            .insert_resource(Location(spec))
+            .insert_resource(catalog)
+            .add_systems(
+                Update,
+                open_map
+                    .in_set(GameplaySet::Port)
+                    .run_if(in_state(GameState::Game)),
+            )
+            .add_systems(OnEnter(MenuState::Map), map_setup)
+            .add_systems(OnExit(MenuState::Map), despawn_screen::<OnMapScreen>)
+            .add_systems(
+                Update,
+                // Mouse input isn't recorded, so keep it out of replays
+                map_action
+                    .run_if(in_state(MenuState::Map))
+                    .run_if(not(resource_exists::<Replay>())),
+            )
+            .add_systems(
+                Update,
+                travel
+                    .after(GameplaySet::Speech)
+                    .run_if(in_state(GameState::Game)),
+            )
+            .add_systems(OnEnter(GameState::Travelling), arrive);
+    }
+}
+
+/// Bring up the sea chart while the boat is docked.
+fn open_map(
+    actions: Res<Input<GameAction>>,
+    mut ev_port_collision: EventReader<PortCollisionEvent>,
+    mut pause_state: ResMut<NextState<PauseState>>,
+    mut menu_state: ResMut<NextState<MenuState>>,
+) {
+    let docked = ev_port_collision.read().count() > 0;
+
+    if docked && actions.just_pressed(GameAction::Map) {
+        pause_state.set(PauseState::Paused);
+        menu_state.set(MenuState::Map);
+    }
+}
+
+fn map_setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    catalog: Res<LocationCatalog>,
+    location: Res<Location>,
+    port: Res<PortStorage>,
+) {
+    let button_style = Style {
+        width: Val::Px(600.0),
+        height: Val::Px(60.0),
+        margin: UiRect::all(Val::Px(8.0)),
+        padding: UiRect::horizontal(Val::Px(10.0)),
+        flex_direction: FlexDirection::Column,
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    };
+    let text_style = TextStyle {
+        font: asset_server.load("fonts/Pixellari.ttf"),
+        font_size: 26.0,
+        color: TEXT_COLOR,
+    };
+    let detail_style = TextStyle {
+        font_size: 18.0,
+        ..text_style.clone()
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.7).into(),
+                ..default()
+            },
+            OnMapScreen,
+        ))
+        .with_children(|parent| {
+            parent.spawn(
+                TextBundle::from_section(
+                    "Sea Chart",
+                    TextStyle {
+                        font_size: 60.0,
+                        ..text_style.clone()
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::bottom(Val::Px(10.0)),
+                    ..default()
+                }),
+            );
+            parent.spawn(
+                TextBundle::from_section(
+                    format!("{:.1} kg sold so far", port.weight),
+                    detail_style.clone(),
+                )
+                .with_style(Style {
+                    margin: UiRect::bottom(Val::Px(10.0)),
+                    ..default()
+                }),
+            );
+
+            for spec in &catalog.locations {
+                let here = spec.kind == location.0.kind;
+                let unlocked = spec.is_unlocked(&port);
+                let (name, detail) = if here {
+                    (
+                        format!("{} (you are here)", spec.name),
+                        spec.description.clone(),
+                    )
+                } else if unlocked {
+                    (spec.name.clone(), spec.description.clone())
+                } else {
+                    (
+                        spec.name.clone(),
+                        format!(
+                            "Sell {} kg of fish at the port to find the way",
+                            spec.unlock
+                        ),
+                    )
+                };
+                let color = if unlocked && !here {
+                    TEXT_COLOR
+                } else {
+                    LOCKED_TEXT_COLOR
+                };
+
+                parent
+                    .spawn((
+                        ButtonBundle {
+                            style: button_style.clone(),
+                            background_color: NORMAL_BUTTON.into(),
+                            ..default()
+                        },
+                        TravelButton(spec.kind),
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section(
+                            name,
+                            TextStyle {
+                                color,
+                                ..text_style.clone()
+                            },
+                        ));
+                        parent.spawn(TextBundle::from_section(
+                            detail,
+                            TextStyle {
+                                color,
+                                ..detail_style.clone()
+                            },
+                        ));
+                    });
+            }
+
+            parent.spawn(NodeBundle::default()).with_children(|parent| {
+                parent
+                    .spawn((
+                        ButtonBundle {
+                            style: Style {
+                                width: Val::Px(200.0),
+                                ..button_style.clone()
+                            },
+                            background_color: NORMAL_BUTTON.into(),
+                            ..default()
+                        },
+                        MapJournalButton,
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section("Journal", text_style.clone()));
+                    });
+                parent
+                    .spawn((
+                        ButtonBundle {
+                            style: Style {
+                                width: Val::Px(200.0),
+                                ..button_style
+                            },
+                            background_color: NORMAL_BUTTON.into(),
+                            ..default()
+                        },
+                        MapBackButton,
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section("Back", text_style));
+                    });
+            });
+        });
+}
+
+fn map_action(
+    mut commands: Commands,
+    travel_query: Query<(&Interaction, &TravelButton), Changed<Interaction>>,
+    journal_query: Query<&Interaction, (Changed<Interaction>, With<MapJournalButton>)>,
+    back_query: Query<&Interaction, (Changed<Interaction>, With<MapBackButton>)>,
+    mut ev_travel: EventWriter<TravelEvent>,
+    mut ev_resume: EventWriter<ResumeEvent>,
+    mut menu_state: ResMut<NextState<MenuState>>,
+) {
+    for (interaction, button) in &travel_query {
+        if *interaction == Interaction::Pressed {
+            ev_travel.send(TravelEvent { location: button.0 });
+        }
+    }
+
+    for interaction in &journal_query {
+        if *interaction == Interaction::Pressed {
+            open_journal(&mut commands, &mut menu_state, MenuState::Map);
+        }
+    }
+
+    for interaction in &back_query {
+        if *interaction == Interaction::Pressed {
+            ev_resume.send(ResumeEvent);
+        }
+    }
+}
+
+/// Set off for somewhere else that's been unlocked. Everything at the old
+/// location is left behind, but the boat, its gear and its catch come along.
+fn travel(
+    mut commands: Commands,
+    mut ev_travel: EventReader<TravelEvent>,
+    catalog: Res<LocationCatalog>,
+    session: Session,
+    mut game_state: ResMut<NextState<GameState>>,
+    mut pause_state: ResMut<NextState<PauseState>>,
+    mut menu_state: ResMut<NextState<MenuState>>,
+) {
+    let Some(destination) = ev_travel
+        .read()
+        .map(|ev| ev.location)
+        .filter(|kind| {
+            *kind != session.location.0.kind && catalog.get(*kind).is_unlocked(&session.port)
+        })
+        .last()
+    else {
+        return;
+    };
+
+    // The player is spawned again on arrival, and picks their gear back up
+    // the same way as from a save
+    commands.insert_resource(LoadedSave(SaveData {
+        location: destination,
+        ..session.snapshot()
+    }));
+    set_location(&mut commands, destination);
+
+    game_state.set(GameState::Travelling);
+    pause_state.set(PauseState::Running);
+    menu_state.set(MenuState::Disabled);
+}
+
+fn arrive(mut game_state: ResMut<NextState<GameState>>) {
+    game_state.set(GameState::Game);
+}
@@ -1,43 +1,28 @@
-mod audio;
-mod camera;
-pub mod components;
-pub mod events;
-pub mod fish;
-pub mod game;
-pub mod menu;
-pub mod player;
-pub mod port;
-pub mod resources;
-pub mod rod;
-pub mod speech;
-pub mod systems;
-pub mod trash;
-mod ui;
-
-use crate::audio::AudioPlugin;
-use crate::camera::CameraPlugin;
-use crate::components::{AnimationIndices, AnimationTimer};
-use crate::game::GamePlugin;
-use crate::menu::MenuPlugin;
-use crate::systems::animate_sprite;
-use crate::ui::UIPlugin;
-use crate::GameState::Game;
-use audio::Volume;
 use bevy::{prelude::*, window::WindowTheme};
-use systems::{pause_the_game, tick_decay_timers};
-
-const TEXT_COLOR: Color = Color::rgb(0.9, 0.9, 0.9);
-
-#[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Hash, States)]
-pub enum GameState {
-    #[default]
-    Menu,
-    Game,
-}
+use gone_fish::{
+    actions::ActionsPlugin,
+    camera::CameraPlugin,
+    debug::DebugPlugin,
+    game::GamePlugin,
+    menu::MenuPlugin,
+    replay::{Recorder, Recording, Replay, ReplayPlugin},
+    resources::{GameRng, Volume},
+    systems::{animate_sprite, tick_decay_timers},
+    ui::UIPlugin,
+    GameState::{self, Game},
+    GameplaySet,
+};
+use std::path::PathBuf;
 
 fn main() {
-    App::new()
-        .insert_resource(Volume(0.5))
+    let args = Args::parse();
+    let replay = args.replay.map(|path| Replay::new(Recording::load(&path)));
+    // A replay has to be played in the world it was recorded in
+    let seed = replay.as_ref().map(Replay::seed).or(args.seed);
+
+    let mut app = App::new();
+    app.insert_resource(Volume(0.5))
+        .insert_resource(GameRng::from_seed_or_entropy(seed))
         .add_state::<GameState>()
         .add_plugins((
             DefaultPlugins
@@ -57,100 +42,88 @@ fn main() {
                     ..default()
                 })
                 .set(ImagePlugin::default_nearest()),
+            ActionsPlugin,
+            ReplayPlugin,
             CameraPlugin,
             MenuPlugin,
             GamePlugin,
             UIPlugin,
-            AudioPlugin,
+            DebugPlugin,
         ))
-        .add_systems(Startup, setup)
         .add_systems(
             Update,
             (
-                tick_decay_timers.run_if(in_state(Game)),
+                tick_decay_timers
+                    .before(GameplaySet::Player)
+                    .run_if(in_state(Game)),
                 animate_sprite,
-                pause_the_game,
             ),
-        )
-        .run();
-}
+        );
 
-fn setup(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
-    window: Query<&mut Window>,
-) {
-    let window = window.single();
-    // From center of screen.
-    let window_width = window.resolution.width() / 2.;
+    #[cfg(feature = "audio")]
+    app.add_plugins(gone_fish::audio::AudioPlugin);
 
-    // Sky
-    commands.spawn(SpriteBundle {
-        texture: asset_server.load("craftpix/clouds/clouds_7/1.png"),
-        transform: Transform {
-            translation: Vec3::new(0., 0., -20.),
-            scale: Vec3::splat(7.),
-            ..default()
-        },
-        ..default()
-    });
+    if let Some(replay) = replay {
+        app.insert_resource(replay);
+    }
+    if let Some(path) = args.record {
+        app.insert_resource(Recorder::new(path));
+    }
 
-    // Border barrel
-    commands.spawn(SpriteBundle {
-        texture: asset_server.load("craftpix/objects/Fishbarrel3.png"),
-        transform: Transform {
-            translation: Vec3::new(window_width + 200., -40., 0.),
-            scale: Vec3::splat(2.),
-            ..default()
-        },
-        ..default()
-    });
+    app.run();
+}
 
-    // Water top
-    let mut initial = -1000.;
-    for _ in 0..100 {
-        let texture_handle = asset_server.load("critters/water_tile.png");
-        let texture_atlas =
-            TextureAtlas::from_grid(texture_handle, Vec2::new(16., 16.), 32, 1, None, None);
-        let texture_atlas_handle = texture_atlases.add(texture_atlas);
-        let animation_indices = AnimationIndices { first: 0, last: 31 };
+#[derive(Default)]
+struct Args {
+    /// Play the world generated from this seed, so one from a bug report
+    /// can be played again.
+    seed: Option<u64>,
+    /// Record the session's input to this file.
+    record: Option<PathBuf>,
+    /// Play back a recorded session from this file.
+    replay: Option<PathBuf>,
+}
 
-        commands.spawn((
-            SpriteSheetBundle {
-                texture_atlas: texture_atlas_handle,
-                sprite: TextureAtlasSprite::new(animation_indices.first),
-                transform: Transform {
-                    translation: Vec3::new(initial, -46., 3.),
-                    scale: Vec3::splat(3.),
-                    ..default()
-                },
-                ..default()
-            },
-            animation_indices,
-            AnimationTimer(Timer::from_seconds(0.2, TimerMode::Repeating)),
-        ));
+const USAGE: &str = "usage: gone-fish [--seed <number>] [--record <path>] [--replay <path>]";
 
-        initial += 48.;
-    }
+impl Args {
+    fn parse() -> Self {
+        let mut args = std::env::args().skip(1);
+        let mut parsed = Args::default();
 
-    // Water
-    commands.spawn(SpriteBundle {
-        sprite: Sprite {
-            color: Color::hex("#7287D5").expect("is a valid colour."),
-            ..default()
-        },
-        transform: Transform {
-            translation: Vec3::new(0., -2050., 0.),
-            scale: Vec3::new(5000., 4000., 0.),
-            ..default()
-        },
-        ..default()
-    });
-}
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--seed" => {
+                    parsed.seed = Some(
+                        args.next()
+                            .and_then(|value| value.parse().ok())
+                            .unwrap_or_else(|| usage_error("--seed needs a number")),
+                    );
+                }
+                "--record" => {
+                    let path = args
+                        .next()
+                        .unwrap_or_else(|| usage_error("--record needs a path"));
+                    parsed.record = Some(path.into());
+                }
+                "--replay" => {
+                    let path = args
+                        .next()
+                        .unwrap_or_else(|| usage_error("--replay needs a path"));
+                    parsed.replay = Some(path.into());
+                }
+                // macOS passes its launcher a process serial number, so
+                // don't refuse to start over it
+                _ if arg.starts_with("-psn_") => {}
+                _ => usage_error(&format!("unknown argument {arg}")),
+            }
+        }
 
-fn despawn_screen<T: Component>(to_despawn: Query<Entity, With<T>>, mut commands: Commands) {
-    for entity in &to_despawn {
-        commands.entity(entity).despawn_recursive();
+        parsed
     }
 }
+
+fn usage_error(message: &str) -> ! {
+    eprintln!("{message}\n{USAGE}");
+    std::process::exit(2);
+}
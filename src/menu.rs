@@ -1,8 +1,15 @@
 use bevy::{app::AppExit, prelude::*};
 
-use crate::audio::Volume;
+use crate::{
+    actions::{Bindings, GameAction},
+    events::ResumeEvent,
+    journal::open_journal,
+    replay::Replay,
+    resources::{GameRng, Volume},
+    save::SaveData,
+};
 
-use super::{despawn_screen, GameState, TEXT_COLOR};
+use super::{despawn_screen, GameState, PauseState, TEXT_COLOR};
 
 pub struct MenuPlugin;
 
@@ -12,6 +19,11 @@ impl Plugin for MenuPlugin {
             .add_systems(OnEnter(GameState::Menu), menu_setup)
             .add_systems(OnEnter(MenuState::Main), main_menu_setup)
             .add_systems(OnExit(MenuState::Main), despawn_screen::<OnMainMenuScreen>)
+            .add_systems(OnEnter(MenuState::Pause), pause_menu_setup)
+            .add_systems(
+                OnExit(MenuState::Pause),
+                despawn_screen::<OnPauseMenuScreen>,
+            )
             .add_systems(OnEnter(MenuState::Settings), settings_menu_setup)
             .add_systems(
                 OnExit(MenuState::Settings),
@@ -26,9 +38,49 @@ impl Plugin for MenuPlugin {
                 OnExit(MenuState::SettingsSound),
                 despawn_screen::<OnSoundSettingsMenuScreen>,
             )
+            .init_resource::<PendingRebind>()
+            .init_resource::<ControlsFocus>()
+            .add_systems(
+                OnEnter(MenuState::SettingsControls),
+                controls_settings_menu_setup,
+            )
+            .add_systems(
+                Update,
+                // Whatever was pressed to start a rebind mustn't be caught as
+                // the new binding, so presses are captured before they can
+                // start one
+                (
+                    capture_binding,
+                    navigate_controls,
+                    rebind_button_action,
+                    update_rebind_labels,
+                )
+                    .chain()
+                    .run_if(in_state(MenuState::SettingsControls)),
+            )
+            .add_systems(
+                OnExit(MenuState::SettingsControls),
+                (
+                    despawn_screen::<OnControlsSettingsMenuScreen>,
+                    cancel_rebind,
+                ),
+            )
             .add_systems(
                 Update,
-                (menu_action, button_system).run_if(in_state(GameState::Menu)),
+                (
+                    menu_action,
+                    // Mouse input isn't recorded, so keep it out of replays
+                    game_menu_action.run_if(not(resource_exists::<Replay>())),
+                )
+                    .run_if(in_state(GameState::Menu).or_else(in_state(PauseState::Paused))),
+            )
+            // Buttons outside the menus, like the shop's, light up the same
+            .add_systems(Update, button_system)
+            .add_systems(
+                Update,
+                highlight_focus
+                    .after(button_system)
+                    .run_if(in_state(MenuState::SettingsControls)),
             );
     }
 }
@@ -36,8 +88,13 @@ impl Plugin for MenuPlugin {
 #[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Hash, States)]
 pub enum MenuState {
     Main,
+    Pause,
     Settings,
     SettingsSound,
+    SettingsControls,
+    Map,
+    Journal,
+    Achievements,
     #[default]
     Disabled,
 }
@@ -45,13 +102,45 @@ pub enum MenuState {
 #[derive(Component)]
 struct OnMainMenuScreen;
 
+#[derive(Component)]
+struct OnPauseMenuScreen;
+
 #[derive(Component)]
 struct OnSettingsMenuScreen;
 
 #[derive(Component)]
 struct OnSoundSettingsMenuScreen;
 
-const NORMAL_BUTTON: Color = Color::rgb(0.15, 0.15, 0.15);
+#[derive(Component)]
+struct OnControlsSettingsMenuScreen;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum InputDevice {
+    Keyboard,
+    Gamepad,
+}
+
+/// Rebinds an action on one device to the next key or button pressed.
+#[derive(Component, Clone, Copy, PartialEq, Debug)]
+struct RebindButton {
+    action: GameAction,
+    device: InputDevice,
+}
+
+/// The binding waiting on a key or button press, if any.
+#[derive(Resource, Default)]
+struct PendingRebind(Option<RebindButton>);
+
+/// Where a button sits on the controls page, in the order it was spawned:
+/// two per action, then reset and back.
+#[derive(Component, Clone, Copy, PartialEq)]
+struct ControlsSlot(usize);
+
+/// The controls page button that keyboard and gamepad input is on.
+#[derive(Resource, Default)]
+struct ControlsFocus(usize);
+
+pub(crate) const NORMAL_BUTTON: Color = Color::rgb(0.15, 0.15, 0.15);
 const HOVERED_BUTTON: Color = Color::rgb(0.25, 0.25, 0.25);
 const HOVERED_PRESSED_BUTTON: Color = Color::WHITE;
 const PRESSED_BUTTON: Color = Color::WHITE;
@@ -59,17 +148,32 @@ const PRESSED_BUTTON: Color = Color::WHITE;
 #[derive(Component)]
 struct SelectedOption;
 
+/// Marks the restart button once it has been pressed with a save on disk,
+/// so it takes a second press to start over and throw the save away.
+#[derive(Component)]
+struct ConfirmRestart;
+
 #[derive(Component)]
 enum MenuButtonAction {
     Play,
+    Continue,
     Quit,
+    Resume,
+    Journal,
+    Achievements,
+    Restart,
+    QuitToTitle,
     Settings,
     SettingsSound,
-    BackToMainMenu,
+    SettingsControls,
+    ResetControls,
+    BackFromSettings,
     BackToSettings,
 }
 
-// This system handles changing all buttons color based on mouse interaction
+// This system handles changing all buttons color based on mouse interaction,
+// wherever they are
+#[allow(clippy::type_complexity)]
 fn button_system(
     mut interaction_query: Query<
         (&Interaction, &mut BackgroundColor, Option<&SelectedOption>),
@@ -90,6 +194,7 @@ fn menu_setup(mut menu_state: ResMut<NextState<MenuState>>) {
     menu_state.set(MenuState::Main);
 }
 
+#[allow(clippy::type_complexity)]
 fn setting_button<T: Resource + Component + PartialEq + Copy + std::fmt::Debug>(
     interaction_query: Query<(&Interaction, &T, Entity), (Changed<Interaction>, With<Button>)>,
     mut selected_query: Query<(Entity, &mut BackgroundColor), With<SelectedOption>>,
@@ -110,7 +215,7 @@ fn setting_button<T: Resource + Component + PartialEq + Copy + std::fmt::Debug>(
 fn main_menu_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
     // Common style for all buttons on the screen
     let button_style = Style {
-        width: Val::Px(250.0),
+        width: Val::Px(300.0),
         height: Val::Px(65.0),
         margin: UiRect::all(Val::Px(20.0)),
         justify_content: JustifyContent::Center,
@@ -159,15 +264,34 @@ fn main_menu_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                             },
                         )
                         .with_style(Style {
-                            margin: UiRect::all(Val::Px(50.0)),
+                            margin: UiRect::all(Val::Px(30.0)),
                             ..default()
                         }),
                     );
 
-                    // Display three buttons for each action available from the main menu:
+                    // Display a button for each action available from the main menu:
+                    // - continue (only when there is a save to pick up)
                     // - new game
+                    // - achievements
                     // - settings
                     // - quit
+                    if SaveData::exists() {
+                        parent
+                            .spawn((
+                                ButtonBundle {
+                                    style: button_style.clone(),
+                                    background_color: NORMAL_BUTTON.into(),
+                                    ..default()
+                                },
+                                MenuButtonAction::Continue,
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section(
+                                    "Continue",
+                                    button_text_style.clone(),
+                                ));
+                            });
+                    }
                     parent
                         .spawn((
                             ButtonBundle {
@@ -183,6 +307,21 @@ fn main_menu_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                                 button_text_style.clone(),
                             ));
                         });
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: button_style.clone(),
+                                background_color: NORMAL_BUTTON.into(),
+                                ..default()
+                            },
+                            MenuButtonAction::Achievements,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn(TextBundle::from_section(
+                                "Achievements",
+                                button_text_style.clone(),
+                            ));
+                        });
                     parent
                         .spawn((
                             ButtonBundle {
@@ -214,6 +353,106 @@ fn main_menu_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
         });
 }
 
+fn pause_menu_setup(mut commands: Commands, asset_server: Res<AssetServer>, rng: Res<GameRng>) {
+    let button_style = Style {
+        width: Val::Px(300.0),
+        height: Val::Px(55.0),
+        margin: UiRect::all(Val::Px(10.0)),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    };
+    let button_text_style = TextStyle {
+        font: asset_server.load("fonts/Pixellari.ttf"),
+        font_size: 40.0,
+        color: TEXT_COLOR,
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                // Dim the game behind the menu
+                background_color: Color::rgba(0., 0., 0., 0.5).into(),
+                ..default()
+            },
+            OnPauseMenuScreen,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(
+                        TextBundle::from_section(
+                            "Paused",
+                            TextStyle {
+                                font: asset_server.load("fonts/Pixellari.ttf"),
+                                font_size: 70.0,
+                                color: TEXT_COLOR,
+                            },
+                        )
+                        .with_style(Style {
+                            margin: UiRect::top(Val::Px(20.0)),
+                            ..default()
+                        }),
+                    );
+                    // So a world worth sharing can be played again with --seed
+                    parent.spawn(
+                        TextBundle::from_section(
+                            format!("Seed: {}", rng.seed()),
+                            TextStyle {
+                                font: asset_server.load("fonts/Pixellari.ttf"),
+                                font_size: 30.0,
+                                color: TEXT_COLOR,
+                            },
+                        )
+                        .with_style(Style {
+                            margin: UiRect::bottom(Val::Px(20.0)),
+                            ..default()
+                        }),
+                    );
+
+                    for (action, text) in [
+                        (MenuButtonAction::Resume, "Resume"),
+                        (MenuButtonAction::Journal, "Journal"),
+                        (MenuButtonAction::Achievements, "Achievements"),
+                        (MenuButtonAction::Settings, "Settings"),
+                        (MenuButtonAction::Restart, "Restart"),
+                        (MenuButtonAction::QuitToTitle, "Quit to Title"),
+                    ] {
+                        parent
+                            .spawn((
+                                ButtonBundle {
+                                    style: button_style.clone(),
+                                    background_color: NORMAL_BUTTON.into(),
+                                    ..default()
+                                },
+                                action,
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section(
+                                    text,
+                                    button_text_style.clone(),
+                                ));
+                            });
+                    }
+                });
+        });
+}
+
 fn settings_menu_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
     let button_style = Style {
         width: Val::Px(200.0),
@@ -257,7 +496,8 @@ fn settings_menu_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                 .with_children(|parent| {
                     for (action, text) in [
                         (MenuButtonAction::SettingsSound, "Sound"),
-                        (MenuButtonAction::BackToMainMenu, "Back"),
+                        (MenuButtonAction::SettingsControls, "Controls"),
+                        (MenuButtonAction::BackFromSettings, "Back"),
                     ] {
                         parent
                             .spawn((
@@ -371,32 +611,411 @@ fn sound_settings_menu_setup(
         });
 }
 
+#[allow(clippy::type_complexity)]
 fn menu_action(
     interaction_query: Query<
         (&Interaction, &MenuButtonAction),
         (Changed<Interaction>, With<Button>),
     >,
+    mut commands: Commands,
     mut ev_app_exit: EventWriter<AppExit>,
     mut menu_state: ResMut<NextState<MenuState>>,
-    mut game_state: ResMut<NextState<GameState>>,
+    pause_state: Res<State<PauseState>>,
+    mut bindings: ResMut<Bindings>,
 ) {
     for (interaction, menu_button_action) in &interaction_query {
         if *interaction == Interaction::Pressed {
             match menu_button_action {
                 MenuButtonAction::Quit => ev_app_exit.send(AppExit),
-                MenuButtonAction::Play => {
-                    game_state.set(GameState::Game);
-                    menu_state.set(MenuState::Disabled);
+                MenuButtonAction::Journal => {
+                    open_journal(&mut commands, &mut menu_state, MenuState::Pause);
                 }
+                MenuButtonAction::Achievements => menu_state.set(MenuState::Achievements),
                 MenuButtonAction::Settings => menu_state.set(MenuState::Settings),
                 MenuButtonAction::SettingsSound => {
                     menu_state.set(MenuState::SettingsSound);
                 }
-                MenuButtonAction::BackToMainMenu => menu_state.set(MenuState::Main),
+                MenuButtonAction::SettingsControls => {
+                    menu_state.set(MenuState::SettingsControls);
+                }
+                MenuButtonAction::ResetControls => {
+                    *bindings = Bindings::default();
+                    bindings.write();
+                }
+                // Settings are reached from the pause menu as well as the main menu
+                MenuButtonAction::BackFromSettings => match pause_state.get() {
+                    PauseState::Paused => menu_state.set(MenuState::Pause),
+                    PauseState::Running => menu_state.set(MenuState::Main),
+                },
                 MenuButtonAction::BackToSettings => {
                     menu_state.set(MenuState::Settings);
                 }
+                MenuButtonAction::Play
+                | MenuButtonAction::Continue
+                | MenuButtonAction::Resume
+                | MenuButtonAction::Restart
+                | MenuButtonAction::QuitToTitle => {}
             }
         }
     }
 }
+
+/// The buttons that start, resume or leave a game, kept apart from the rest
+/// so replays can be left to do that themselves.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+fn game_menu_action(
+    interaction_query: Query<
+        (Entity, &Interaction, &MenuButtonAction, Has<ConfirmRestart>),
+        (Changed<Interaction>, With<Button>),
+    >,
+    children_query: Query<&Children>,
+    mut text_query: Query<&mut Text>,
+    mut commands: Commands,
+    mut menu_state: ResMut<NextState<MenuState>>,
+    mut game_state: ResMut<NextState<GameState>>,
+    mut next_pause_state: ResMut<NextState<PauseState>>,
+    mut ev_resume: EventWriter<ResumeEvent>,
+) {
+    for (entity, interaction, menu_button_action, confirmed) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            match menu_button_action {
+                MenuButtonAction::Play => {
+                    game_state.set(GameState::Game);
+                    menu_state.set(MenuState::Disabled);
+                }
+                MenuButtonAction::Continue => {
+                    if let Some(save) = SaveData::load() {
+                        save.apply(&mut commands);
+                    }
+                    game_state.set(GameState::Game);
+                    menu_state.set(MenuState::Disabled);
+                }
+                MenuButtonAction::Resume => ev_resume.send(ResumeEvent),
+                // Starting over costs the save, so make sure it's meant
+                MenuButtonAction::Restart if !confirmed && SaveData::exists() => {
+                    commands.entity(entity).insert(ConfirmRestart);
+                    let children = children_query
+                        .get(entity)
+                        .expect("every menu button to have a label");
+                    if let Ok(mut text) = text_query.get_mut(children[0]) {
+                        text.sections[0].value = "Erase save?".into();
+                    }
+                }
+                MenuButtonAction::Restart => {
+                    SaveData::delete();
+                    game_state.set(GameState::Restarting);
+                    next_pause_state.set(PauseState::Running);
+                    menu_state.set(MenuState::Disabled);
+                }
+                // Entering the menu state brings up the main menu
+                MenuButtonAction::QuitToTitle => {
+                    game_state.set(GameState::Menu);
+                    next_pause_state.set(PauseState::Running);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn controls_settings_menu_setup(
+    mut commands: Commands,
+    bindings: Res<Bindings>,
+    pending: Res<PendingRebind>,
+    mut focus: ResMut<ControlsFocus>,
+    asset_server: Res<AssetServer>,
+) {
+    focus.0 = 0;
+    let mut slot = 0;
+
+    let button_style = Style {
+        width: Val::Px(250.0),
+        height: Val::Px(50.0),
+        margin: UiRect::all(Val::Px(8.0)),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    };
+    let button_text_style = TextStyle {
+        font: asset_server.load("fonts/Pixellari.ttf"),
+        font_size: 30.0,
+        color: TEXT_COLOR,
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            OnControlsSettingsMenuScreen,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for action in GameAction::ALL {
+                        parent
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    align_items: AlignItems::Center,
+                                    ..default()
+                                },
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                parent.spawn(
+                                    TextBundle::from_section(
+                                        action.description(),
+                                        button_text_style.clone(),
+                                    )
+                                    .with_style(Style {
+                                        width: Val::Px(250.0),
+                                        ..default()
+                                    }),
+                                );
+
+                                for device in [InputDevice::Keyboard, InputDevice::Gamepad] {
+                                    let rebind = RebindButton { action, device };
+
+                                    parent
+                                        .spawn((
+                                            ButtonBundle {
+                                                style: button_style.clone(),
+                                                background_color: NORMAL_BUTTON.into(),
+                                                ..default()
+                                            },
+                                            rebind,
+                                            ControlsSlot(slot),
+                                        ))
+                                        .with_children(|parent| {
+                                            parent.spawn(TextBundle::from_section(
+                                                rebind_label(rebind, &bindings, &pending),
+                                                button_text_style.clone(),
+                                            ));
+                                        });
+                                    slot += 1;
+                                }
+                            });
+                    }
+
+                    for (action, text) in [
+                        (MenuButtonAction::ResetControls, "Reset to defaults"),
+                        (MenuButtonAction::BackToSettings, "Back"),
+                    ] {
+                        parent
+                            .spawn((
+                                ButtonBundle {
+                                    style: button_style.clone(),
+                                    background_color: NORMAL_BUTTON.into(),
+                                    ..default()
+                                },
+                                action,
+                                ControlsSlot(slot),
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section(
+                                    text,
+                                    button_text_style.clone(),
+                                ));
+                            });
+                        slot += 1;
+                    }
+
+                    parent.spawn(TextBundle::from_section(
+                        "Escape or B cancels a rebind",
+                        TextStyle {
+                            font_size: 20.0,
+                            ..button_text_style.clone()
+                        },
+                    ));
+                });
+        });
+}
+
+fn rebind_label(rebind: RebindButton, bindings: &Bindings, pending: &PendingRebind) -> String {
+    match (rebind.device, pending.0 == Some(rebind)) {
+        (InputDevice::Keyboard, true) => "Press a key...".into(),
+        (InputDevice::Gamepad, true) => "Press a button...".into(),
+        (InputDevice::Keyboard, false) => bindings.key_name(rebind.action),
+        (InputDevice::Gamepad, false) => bindings.button_name(rebind.action),
+    }
+}
+
+fn rebind_button_action(
+    interaction_query: Query<(&Interaction, &RebindButton), Changed<Interaction>>,
+    mut pending: ResMut<PendingRebind>,
+) {
+    for (interaction, rebind) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            pending.0 = Some(*rebind);
+        }
+    }
+}
+
+/// Move between the controls page buttons with the arrow keys or d-pad, and
+/// press the one focused with enter or A.
+fn navigate_controls(
+    mut focus: ResMut<ControlsFocus>,
+    mut pending: ResMut<PendingRebind>,
+    mut bindings: ResMut<Bindings>,
+    mut menu_state: ResMut<NextState<MenuState>>,
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepad_input: Res<Input<GamepadButton>>,
+    rebind_query: Query<(&ControlsSlot, &RebindButton)>,
+) {
+    // Presses made while rebinding, or that just finished one, belong to it
+    if pending.0.is_some() || pending.is_changed() {
+        return;
+    }
+
+    let pressed = |key, button| {
+        keyboard_input.just_pressed(key)
+            || gamepad_input
+                .get_just_pressed()
+                .any(|pressed| pressed.button_type == button)
+    };
+    // Slots past the rebind buttons run one to a row
+    let rebinds = GameAction::ALL.len() * 2;
+    let last = rebinds + 1;
+    let slot = focus.0;
+
+    if pressed(KeyCode::Up, GamepadButtonType::DPadUp) {
+        focus.0 = match slot {
+            _ if slot < 2 => slot,
+            _ if slot < rebinds => slot - 2,
+            _ if slot == rebinds => rebinds - 2,
+            _ => slot - 1,
+        };
+    } else if pressed(KeyCode::Down, GamepadButtonType::DPadDown) {
+        focus.0 = match slot {
+            _ if slot + 2 < rebinds => slot + 2,
+            _ if slot < rebinds => rebinds,
+            _ => (slot + 1).min(last),
+        };
+    } else if (pressed(KeyCode::Left, GamepadButtonType::DPadLeft)
+        || pressed(KeyCode::Right, GamepadButtonType::DPadRight))
+        && slot < rebinds
+    {
+        focus.0 = slot ^ 1;
+    } else if pressed(KeyCode::Return, GamepadButtonType::South) {
+        match slot {
+            _ if slot == rebinds => {
+                *bindings = Bindings::default();
+                bindings.write();
+            }
+            _ if slot == last => menu_state.set(MenuState::Settings),
+            _ => {
+                pending.0 = rebind_query
+                    .iter()
+                    .find(|(rebind_slot, _)| rebind_slot.0 == slot)
+                    .map(|(_, rebind)| *rebind);
+            }
+        }
+    }
+}
+
+/// Light up the focused controls page button, following the mouse onto
+/// whichever button it hovers.
+fn highlight_focus(
+    mut focus: ResMut<ControlsFocus>,
+    mut button_query: Query<(&ControlsSlot, &Interaction, &mut BackgroundColor)>,
+) {
+    if let Some((slot, ..)) = button_query
+        .iter()
+        .find(|(_, interaction, _)| **interaction == Interaction::Hovered)
+    {
+        if focus.0 != slot.0 {
+            focus.0 = slot.0;
+        }
+    }
+
+    for (slot, interaction, mut color) in &mut button_query {
+        if *interaction != Interaction::None {
+            continue;
+        }
+        let wanted = if slot.0 == focus.0 {
+            HOVERED_BUTTON
+        } else {
+            NORMAL_BUTTON
+        };
+        if color.0 != wanted {
+            color.0 = wanted;
+        }
+    }
+}
+
+/// Bind whatever is pressed next to the action waiting on it, or give up on
+/// it if escape or B is pressed.
+fn capture_binding(
+    mut pending: ResMut<PendingRebind>,
+    mut bindings: ResMut<Bindings>,
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepad_input: Res<Input<GamepadButton>>,
+) {
+    let Some(RebindButton { action, device }) = pending.0 else {
+        return;
+    };
+
+    if keyboard_input.just_pressed(KeyCode::Escape)
+        || gamepad_input
+            .get_just_pressed()
+            .any(|button| button.button_type == GamepadButtonType::East)
+    {
+        pending.0 = None;
+        return;
+    }
+
+    match device {
+        InputDevice::Keyboard => {
+            let Some(key) = keyboard_input.get_just_pressed().next() else {
+                return;
+            };
+            bindings.bind_key(action, *key);
+        }
+        InputDevice::Gamepad => {
+            let Some(button) = gamepad_input.get_just_pressed().next() else {
+                return;
+            };
+            bindings.bind_button(action, button.button_type);
+        }
+    }
+
+    bindings.write();
+    pending.0 = None;
+}
+
+fn update_rebind_labels(
+    bindings: Res<Bindings>,
+    pending: Res<PendingRebind>,
+    button_query: Query<(&RebindButton, &Children)>,
+    mut text_query: Query<&mut Text>,
+) {
+    if !bindings.is_changed() && !pending.is_changed() {
+        return;
+    }
+
+    for (rebind, children) in &button_query {
+        let mut text = text_query
+            .get_mut(children[0])
+            .expect("rebind buttons hold their label");
+        text.sections[0].value = rebind_label(*rebind, &bindings, &pending);
+    }
+}
+
+fn cancel_rebind(mut pending: ResMut<PendingRebind>) {
+    pending.0 = None;
+}
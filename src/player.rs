@@ -1,10 +1,16 @@
 use crate::{
-    components::{AnimationIndices, AnimationTimer, FishStorage},
-    events::PortCollisionEvent,
+    actions::GameAction,
+    components::{AnimationIndices, AnimationTimer, FishStorage, Hitbox, Velocity},
+    events::{PortCollisionEvent, RoughWaterEvent},
+    game::OnGameScreen,
     port::Port,
     resources::PlayerFishStored,
     rod::RodVariant,
+    spoilage::{IceBox, DEFAULT_ICE_BOX},
+    weather::Weather,
+    world::WorldMap,
     GameState::Game,
+    GameplaySet,
 };
 use bevy::prelude::*;
 use bevy::sprite::collide_aabb::collide;
@@ -23,15 +29,39 @@ pub enum PlayerState {
 #[derive(Component)]
 pub struct Boat;
 
+/// Top speed the boat can be rowed at with an empty hold, in px/s. Raised by
+/// boat upgrades.
+#[derive(Component)]
+pub struct BoatSpeed(pub f32);
+
+pub const DEFAULT_BOAT_SPEED: f32 = 150.;
+/// Where the boat sets out from at the start of a session.
+pub const BOAT_START: f32 = 0.;
+/// Mass of the boat and fisherman, in kg. The catch in the hold adds to it,
+/// so a loaded boat is slower to get going and to stop.
+const BOAT_MASS: f32 = 200.;
+/// How hard the water holds the boat back, in kg/s. Over the boat's mass,
+/// this is how quickly it picks up or loses speed.
+const BOAT_DRAG: f32 = 400.;
+/// How much of its top speed the boat loses with a full hold.
+const FULL_HOLD_SLOWDOWN: f32 = 0.3;
+/// How much of the wind's speed it pushes the boat at.
+const BOAT_WIND_PUSH: f32 = 0.4;
+const BOAT_SIZE: Vec2 = Vec2::new(74., 18.);
+
 pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<PlayerFishStored>()
+        app.add_event::<RoughWaterEvent>()
+            .init_resource::<PlayerFishStored>()
             .add_systems(OnEnter(Game), setup)
             .add_systems(
                 Update,
-                (player_movement, check_for_port_collisions).run_if(in_state(Game)),
+                (player_movement, check_for_port_collisions)
+                    .chain()
+                    .in_set(GameplaySet::Player)
+                    .run_if(in_state(Game)),
             );
     }
 }
@@ -44,6 +74,7 @@ pub fn setup(
     let boat = commands
         .spawn((
             Boat,
+            Hitbox(BOAT_SIZE),
             SpriteBundle {
                 texture: asset_server.load("craftpix/objects/Boat.png"),
                 transform: Transform {
@@ -66,8 +97,12 @@ pub fn setup(
 
             (
                 Player,
+                OnGameScreen,
                 PlayerState::Rowing,
                 RodVariant::StickWithString,
+                BoatSpeed(DEFAULT_BOAT_SPEED),
+                IceBox(DEFAULT_ICE_BOX),
+                Velocity(Vec3::ZERO),
                 FishStorage {
                     current: 0.,
                     max: 3.,
@@ -76,7 +111,7 @@ pub fn setup(
                     texture_atlas: texture_atlas_handle,
                     sprite: TextureAtlasSprite::new(animation_indices.first),
                     transform: Transform {
-                        translation: Vec3::new(0., 0., 5.),
+                        translation: Vec3::new(BOAT_START, 0., 5.),
                         scale: Vec3::splat(3.),
                         ..default()
                     },
@@ -91,60 +126,87 @@ pub fn setup(
     commands.entity(player).push_children(&[boat]);
 }
 
+/// Row the boat. Rowing and the wind push it towards a speed, and the water's
+/// drag eases it there, so the boat builds up speed and coasts to a stop
+/// rather than starting and stopping dead. It can't go further out than its
+/// top speed gets it.
 fn player_movement(
     time: Res<Time>,
-    keyboard_input: Res<Input<KeyCode>>,
-    window: Query<&mut Window>,
-    mut player_query: Query<(&mut Transform, &mut PlayerState), With<Player>>,
-    mut camera_query: Query<&mut Transform, (With<Camera>, Without<Player>)>,
+    actions: Res<Input<GameAction>>,
+    weather: Res<Weather>,
+    map: Res<WorldMap>,
+    mut ev_rough_water: EventWriter<RoughWaterEvent>,
+    mut against_swell: Local<bool>,
+    mut player_query: Query<
+        (
+            &mut Transform,
+            &mut Velocity,
+            &mut PlayerState,
+            &BoatSpeed,
+            &FishStorage,
+        ),
+        With<Player>,
+    >,
 ) {
-    let (mut transform, mut player_state) = player_query.single_mut();
-    let window = window.single();
-    let mut camera = camera_query.single_mut();
-
-    // From center of screen.
-    let window_width = window.resolution.width() / 2.;
-    // From center of player.
-    let player_width = transform.scale.truncate().x / 2.;
-
-    if transform.translation.x - player_width > -window_width
-        && keyboard_input.pressed(KeyCode::Left)
-    {
-        *player_state = PlayerState::Rowing;
-        transform.translation.x -= 150. * time.delta_seconds();
-        camera.translation.x -= 150. * time.delta_seconds();
+    let (mut transform, mut velocity, mut player_state, boat_speed, storage) =
+        player_query.single_mut();
+
+    let mut rowing = 0.;
+    if actions.pressed(GameAction::MoveLeft) {
+        rowing -= 1.;
+    }
+    if actions.pressed(GameAction::MoveRight) {
+        rowing += 1.;
     }
 
-    if transform.translation.x + player_width < window_width
-        && keyboard_input.pressed(KeyCode::Right)
-    {
-        *player_state = PlayerState::Rowing;
-        transform.translation.x += 150. * time.delta_seconds();
-        camera.translation.x += 150. * time.delta_seconds();
+    *player_state = if rowing == 0. {
+        PlayerState::Idle
+    } else {
+        PlayerState::Rowing
+    };
+
+    let load = (storage.current / storage.max).clamp(0., 1.);
+    let top_speed = boat_speed.0 * (1. - FULL_HOLD_SLOWDOWN * load);
+    let mass = BOAT_MASS + storage.current;
+    let target = rowing * top_speed + weather.wind * BOAT_WIND_PUSH;
+
+    velocity.0.x =
+        target + (velocity.0.x - target) * (-BOAT_DRAG / mass * time.delta_seconds()).exp();
+    transform.translation.x += velocity.0.x * time.delta_seconds();
+
+    // Pull up at the port inshore, and at the swell past the last fishing
+    // ground the boat can make it through
+    let reach = map.reach(boat_speed.0);
+    if transform.translation.x < map.port {
+        transform.translation.x = map.port;
+        velocity.0.x = 0.;
     }
 
-    *player_state = PlayerState::Idle;
+    let at_reach = transform.translation.x >= reach;
+    if at_reach {
+        transform.translation.x = reach;
+        velocity.0.x = 0.;
+
+        if !*against_swell && reach < map.end() {
+            ev_rough_water.send_default();
+        }
+    }
+    *against_swell = at_reach;
 }
 
-fn check_for_port_collisions(
+pub fn check_for_port_collisions(
     mut player_query: Query<&Transform, With<Player>>,
-    mut port_query: Query<(&Transform, &Handle<Image>), With<Port>>,
+    mut port_query: Query<(&Transform, &Hitbox), With<Port>>,
     mut ev_port_collision: EventWriter<PortCollisionEvent>,
-    assets: Res<Assets<Image>>,
 ) {
     let player = player_query.single_mut();
-    let (port, image) = port_query.single_mut();
+    let (port, port_hitbox) = port_query.single_mut();
 
     if let Some(collision) = collide(
         player.translation,
         player.scale.truncate(),
         port.translation,
-        assets
-            .get(image)
-            .expect("port to always have an available image")
-            .size()
-            .as_vec2()
-            * port.scale.truncate(),
+        port_hitbox.0 * port.scale.truncate(),
     ) {
         ev_port_collision.send(PortCollisionEvent {
             collision_direction: collision,
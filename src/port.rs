@@ -1,11 +1,16 @@
-use crate::events::{DepositFishEvent, UpgradeEvent};
+use crate::events::{ContractCompletedEvent, DepositFishEvent, UpgradeEvent};
 use crate::{
-    components::FishStorage,
+    components::{FishStorage, Hitbox},
+    contracts::ContractBoard,
     events::PortCollisionEvent,
+    fish::FishRegistry,
+    game::OnGameScreen,
     player::Player,
     resources::{PlayerFishStored, PortStorage},
-    rod::RodVariant,
+    spoilage,
+    world::WorldMap,
     GameState::Game,
+    GameplaySet,
 };
 use bevy::prelude::*;
 
@@ -15,6 +20,8 @@ pub struct Port;
 #[derive(Component)]
 pub struct PortUI;
 
+const PORT_SIZE: Vec2 = Vec2::new(192., 122.);
+
 pub struct PortPlugin;
 
 impl Plugin for PortPlugin {
@@ -23,31 +30,32 @@ impl Plugin for PortPlugin {
             .add_event::<PortCollisionEvent>()
             .add_event::<DepositFishEvent>()
             .add_event::<UpgradeEvent>()
-            .add_systems(Startup, setup)
-            .add_systems(OnEnter(Game), setup_port_ui)
+            .add_systems(OnEnter(Game), setup)
             .add_systems(
                 Update,
-                (check_for_port_collisions, update_port_ui).run_if(in_state(Game)),
+                (check_for_port_collisions, update_port_ui)
+                    .chain()
+                    .in_set(GameplaySet::Port)
+                    .run_if(in_state(Game)),
             );
     }
 }
 
 fn setup(
     mut commands: Commands,
-    window: Query<&mut Window>,
+    map: Res<WorldMap>,
     asset_server: Res<AssetServer>,
     mut animations: ResMut<Assets<AnimationClip>>,
+    port_fish: Res<PortStorage>,
 ) {
-    let window = window.single();
-    // From center of screen.
-    let window_width = window.resolution.width() / 2.;
-
     commands.spawn((
         Port,
+        Hitbox(PORT_SIZE),
+        OnGameScreen,
         SpriteBundle {
             texture: asset_server.load("craftpix/objects/Fishing_hut.png"),
             transform: Transform {
-                translation: Vec3::new(-window_width, 0., -10.),
+                translation: Vec3::new(map.port, 0., -10.),
                 scale: Vec3::splat(3.),
                 ..default()
             },
@@ -85,25 +93,31 @@ fn setup(
     let mut player = AnimationPlayer::default();
     player.play(animations.add(animation)).repeat();
 
+    let mut storage_text = Text::from_sections([
+        TextSection::from_style(ui_text_style.clone()),
+        TextSection::from_style(ui_text_style),
+    ])
+    .with_alignment(TextAlignment::Center);
+    write_port_ui(&port_fish, &mut storage_text);
+
     commands
-        .spawn(SpriteBundle {
-            texture: asset_server.load("craftpix/objects/Fishbarrel2.png"),
-            transform: Transform {
-                translation: Vec3::new(-window_width + 150., 17., -9.),
-                scale: Vec3::splat(3.),
+        .spawn((
+            SpriteBundle {
+                texture: asset_server.load("craftpix/objects/Fishbarrel2.png"),
+                transform: Transform {
+                    translation: Vec3::new(map.port + 150., 17., -9.),
+                    scale: Vec3::splat(3.),
+                    ..default()
+                },
                 ..default()
             },
-            ..default()
-        })
+            OnGameScreen,
+        ))
         // Fish Storage
         .with_children(|parent| {
             parent.spawn((
                 Text2dBundle {
-                    text: Text::from_sections([
-                        TextSection::from_style(ui_text_style.clone()),
-                        TextSection::from_style(ui_text_style.clone()),
-                    ])
-                    .with_alignment(TextAlignment::Center),
+                    text: storage_text,
                     transform: Transform {
                         translation: Default::default(),
                         rotation: Default::default(),
@@ -118,115 +132,62 @@ fn setup(
         });
 }
 
-fn check_for_port_collisions(
+#[allow(clippy::too_many_arguments)]
+pub fn check_for_port_collisions(
     mut ev_deposit: EventWriter<DepositFishEvent>,
-    mut ev_upgrade: EventWriter<UpgradeEvent>,
+    mut ev_contract_completed: EventWriter<ContractCompletedEvent>,
     mut ev_port_collison: EventReader<PortCollisionEvent>,
+    registry: Res<FishRegistry>,
     mut port_fish: ResMut<PortStorage>,
     mut player_fish: ResMut<PlayerFishStored>,
-    mut player_query: Query<(&mut FishStorage, &mut RodVariant), With<Player>>,
+    mut contracts: ResMut<ContractBoard>,
+    mut player_query: Query<&mut FishStorage, With<Player>>,
 ) {
     if player_fish.fish.is_empty() {
         return;
     }
 
     for _ in ev_port_collison.read() {
+        let mut coins_earned = 0;
+
         for fish in &player_fish.fish {
-            if let Some(count) = port_fish.fish.get_mut(&fish.0) {
+            if let Some(count) = port_fish.fish.get_mut(&fish.variant) {
                 *count += 1;
             }
+
+            coins_earned += spoilage::sale_price(&registry, fish);
         }
 
-        player_fish.fish.clear();
+        for contract in contracts.deliver(&player_fish.fish) {
+            port_fish.coins += contract.reward;
+            ev_contract_completed.send(ContractCompletedEvent { contract });
+        }
 
-        let (mut player_storage, mut rod_variant) = player_query.single_mut();
-        port_fish.weight += player_storage.current;
+        player_fish.fish.clear();
 
-        player_storage.current = 0.;
+        let mut player_storage = player_query.single_mut();
+        let weight = player_storage.current;
+        port_fish.weight += weight;
+        port_fish.coins += coins_earned;
 
-        // Trigger Upgrades
-        let (new_max, next_upgrade) = match port_fish.weight {
-            w if w > 1200. => {
-                if *rod_variant != RodVariant::CarbonCaster9000 {
-                    ev_upgrade.send_default();
-                }
-                *rod_variant = RodVariant::CarbonCaster9000;
-                (Some(1000.), Some(9999.))
-            }
-            w if w > 200. => {
-                if *rod_variant != RodVariant::GraphiteGuardian {
-                    ev_upgrade.send_default();
-                }
-                *rod_variant = RodVariant::GraphiteGuardian;
-                (Some(200.), Some(1000.))
-            }
-            w if w > 150. => {
-                if *rod_variant != RodVariant::FiberFusion {
-                    ev_upgrade.send_default();
-                }
-                *rod_variant = RodVariant::FiberFusion;
-                (Some(100.), Some(200.))
-            }
-            w if w > 50. => {
-                if *rod_variant != RodVariant::BambooBlisscaster {
-                    ev_upgrade.send_default();
-                }
-                *rod_variant = RodVariant::BambooBlisscaster;
-                (Some(70.), Some(150.))
-            }
-            w if w > 15. => {
-                if *rod_variant != RodVariant::WillowWhiskerWeaver {
-                    ev_upgrade.send_default();
-                }
-                *rod_variant = RodVariant::WillowWhiskerWeaver;
-                (Some(30.), Some(50.))
-            }
-            w if w > 8. => {
-                if *rod_variant != RodVariant::ReedReelRig {
-                    ev_upgrade.send_default();
-                }
-                *rod_variant = RodVariant::ReedReelRig;
-                (Some(10.), Some(15.))
-            }
-            w if w > 4. => {
-                if *rod_variant != RodVariant::TwigAndTwineTackler {
-                    ev_upgrade.send_default();
-                }
-                *rod_variant = RodVariant::TwigAndTwineTackler;
-                (Some(6.), Some(8.))
-            }
-            _ => (None, None),
-        };
+        FishStorage::update_storage(0., None, &mut player_storage);
 
         ev_deposit.send(DepositFishEvent {
             port_weight: port_fish.weight,
-            new_max,
-            next_upgrade,
+            weight,
+            coins_earned,
+            coins: port_fish.coins,
         });
-
-        FishStorage::update_storage(0., new_max, &mut player_storage);
     }
 }
 
-fn setup_port_ui(mut port_ui_query: Query<&mut Text, With<PortUI>>) {
-    let mut stui = port_ui_query.single_mut();
-
-    stui.sections[0].value = "0.00 kg /".into();
-    stui.sections[1].value = " 4 kg".into();
+fn update_port_ui(port_fish: Res<PortStorage>, mut port_ui_query: Query<&mut Text, With<PortUI>>) {
+    if port_fish.is_changed() {
+        write_port_ui(&port_fish, &mut port_ui_query.single_mut());
+    }
 }
 
-fn update_port_ui(
-    mut ev_deposit: EventReader<DepositFishEvent>,
-    mut port_ui_query: Query<&mut Text, With<PortUI>>,
-) {
-    for event in ev_deposit.read() {
-        let current = event.port_weight;
-        let mut stui = port_ui_query.single_mut();
-
-        stui.sections[0].value = format!("{:.2} kg /", current);
-
-        if let Some(next_upgrade) = event.next_upgrade {
-            stui.sections[1].value = format!(" {:?} kg", next_upgrade);
-        }
-    }
+fn write_port_ui(port_fish: &PortStorage, stui: &mut Text) {
+    stui.sections[0].value = format!("{:.2} kg\n", port_fish.weight);
+    stui.sections[1].value = format!("{} coins", port_fish.coins);
 }
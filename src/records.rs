@@ -0,0 +1,136 @@
+use crate::{
+    events::{CatchFishEvent, DepositFishEvent, NewRecordEvent},
+    fish::{FishRegistry, FishVariant},
+    replay::Replay,
+    save::{data_path, write_data_file},
+    GameState::Game,
+    GameplaySet,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs};
+
+/// A personal best that has just been beaten.
+#[derive(Clone, Copy, Debug)]
+pub enum Record {
+    /// Heaviest of a species landed, in kg.
+    Heaviest(FishVariant, f32),
+    /// Most kg handed over at the port in one go.
+    Haul(f32),
+    /// Furthest down a fish has taken the hook and been landed.
+    Deepest(f32),
+}
+
+impl Record {
+    pub fn describe(&self, registry: &FishRegistry) -> String {
+        match self {
+            Record::Heaviest(variant, weight) => {
+                format!("Heaviest {}: {weight:.2} kg", registry.get(*variant).name)
+            }
+            Record::Haul(weight) => format!("Biggest haul: {weight:.2} kg"),
+            Record::Deepest(depth) => format!("Deepest catch: {depth:.0} down"),
+        }
+    }
+}
+
+/// The player's personal bests over every game they've played, kept in their
+/// own file rather than with the save. Starting a new game or restarting
+/// leaves them be, so there's always something to beat.
+#[derive(Resource, Serialize, Deserialize, Default, Debug)]
+pub struct Records {
+    pub heaviest: HashMap<FishVariant, f32>,
+    pub largest_haul: f32,
+    pub deepest: f32,
+}
+
+impl Records {
+    const FILE: &'static str = "records.ron";
+
+    /// The saved records, or none if nothing has been set yet.
+    pub fn load() -> Self {
+        let Some(path) = data_path(Self::FILE).filter(|path| path.exists()) else {
+            return Self::default();
+        };
+
+        fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|contents| ron::from_str(&contents).map_err(|e| e.to_string()))
+            .unwrap_or_else(|e| {
+                error!("failed to load records {}: {e}", path.display());
+                Self::default()
+            })
+    }
+
+    pub fn write(&self) {
+        write_data_file(Self::FILE, self);
+    }
+}
+
+pub struct RecordsPlugin;
+
+impl Plugin for RecordsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Records::load())
+            .add_event::<NewRecordEvent>()
+            .add_systems(
+                Update,
+                (
+                    (check_catch_records, check_haul_records)
+                        .after(GameplaySet::Port)
+                        .before(GameplaySet::Speech),
+                    write_records.run_if(resource_changed::<Records>()),
+                )
+                    // Replays shouldn't count towards the player's real records
+                    .run_if(not(resource_exists::<Replay>()))
+                    .run_if(in_state(Game)),
+            );
+    }
+}
+
+/// Raise `best` to `value` if it beats it. Only a record that was already
+/// set counts as broken, so the first of anything isn't announced.
+fn beat(best: &mut f32, value: f32) -> bool {
+    let broken = *best > 0. && value > *best;
+    *best = best.max(value);
+    broken
+}
+
+fn check_catch_records(
+    mut ev_catch_fish: EventReader<CatchFishEvent>,
+    mut ev_new_record: EventWriter<NewRecordEvent>,
+    mut records: ResMut<Records>,
+) {
+    for ev in ev_catch_fish.read() {
+        let weight = ev.weight.current;
+        let heaviest = records.heaviest.entry(ev.fish_variant).or_insert(0.);
+        if beat(heaviest, weight) {
+            ev_new_record.send(NewRecordEvent {
+                record: Record::Heaviest(ev.fish_variant, weight),
+            });
+        }
+
+        if beat(&mut records.deepest, ev.depth) {
+            ev_new_record.send(NewRecordEvent {
+                record: Record::Deepest(ev.depth),
+            });
+        }
+    }
+}
+
+fn check_haul_records(
+    mut ev_deposit: EventReader<DepositFishEvent>,
+    mut ev_new_record: EventWriter<NewRecordEvent>,
+    mut records: ResMut<Records>,
+) {
+    for ev in ev_deposit.read() {
+        if beat(&mut records.largest_haul, ev.weight) {
+            ev_new_record.send(NewRecordEvent {
+                record: Record::Haul(ev.weight),
+            });
+        }
+    }
+}
+
+fn write_records(records: Res<Records>) {
+    records.write();
+}
@@ -0,0 +1,342 @@
+use crate::{
+    actions::{set_actions, ActionSystem, GameAction},
+    bait::BaitCatalog,
+    events::{BuyBaitEvent, BuyUpgradeEvent, ResumeEvent, TravelEvent},
+    location::LocationKind,
+    menu::MenuState,
+    resources::GameRng,
+    save::{LoadedSave, SaveData},
+    shop::ShopCatalog,
+    GameState,
+};
+use bevy::{
+    app::AppExit,
+    prelude::*,
+    time::{TimeSystem, TimeUpdateStrategy},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// Bump this whenever [`Recording`] changes shape. Older recordings are
+/// still played, but gameplay changes may make them drift from the original
+/// session.
+pub const RECORDING_VERSION: u32 = 6;
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<BuyUpgradeEvent>()
+            .add_event::<BuyBaitEvent>()
+            .add_event::<ResumeEvent>()
+            .add_event::<TravelEvent>()
+            .add_systems(
+                OnEnter(GameState::Game),
+                start_recording.run_if(resource_exists::<Recorder>()),
+            )
+            // Travelling carries the session on, and the recording with
+            // it. The state has already moved on by the time this runs
+            .add_systems(
+                OnExit(GameState::Game),
+                finish_recording
+                    .run_if(resource_exists::<Recorder>())
+                    .run_if(not(in_state(GameState::Travelling))),
+            )
+            .add_systems(
+                Last,
+                (record_frame, write_recording)
+                    .chain()
+                    .run_if(resource_exists::<Recorder>()),
+            )
+            .add_systems(
+                Update,
+                start_replay
+                    .run_if(in_state(GameState::Menu))
+                    .run_if(resource_exists::<Replay>()),
+            )
+            .add_systems(
+                First,
+                next_replay_frame
+                    .before(TimeSystem)
+                    .run_if(resource_exists::<Replay>()),
+            )
+            .add_systems(
+                PreUpdate,
+                play_replay_frame
+                    .in_set(ActionSystem)
+                    .run_if(resource_exists::<Replay>()),
+            );
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RecordedFrame {
+    /// Real time since the previous frame.
+    pub delta: Duration,
+    /// Actions held down during the frame.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub actions: Vec<GameAction>,
+    /// Shop ladders bought from during the frame.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub purchases: Vec<usize>,
+    /// Bait bought during the frame, by index in the bait catalog.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bait_purchases: Vec<usize>,
+    /// Whether the game was resumed from the pause menu during the frame.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub resumed: bool,
+    /// Where the boat set off for from the sea chart during the frame.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub travelled: Option<LocationKind>,
+}
+
+/// A session's input frame by frame, along with everything else needed to
+/// play it back exactly.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Recording {
+    pub version: u32,
+    pub seed: u64,
+    /// The save the session was continued from, if it wasn't a new game.
+    pub save: Option<SaveData>,
+    pub frames: Vec<RecordedFrame>,
+}
+
+impl Recording {
+    pub fn load(path: &Path) -> Self {
+        let contents = fs::read_to_string(path)
+            .unwrap_or_else(|e| panic!("failed to read recording {}: {e}", path.display()));
+        let recording: Recording = ron::from_str(&contents)
+            .unwrap_or_else(|e| panic!("failed to parse recording {}: {e}", path.display()));
+
+        if recording.version != RECORDING_VERSION {
+            warn!(
+                "recording is from version {}, it may not play out as recorded",
+                recording.version
+            );
+        }
+
+        recording.check_purchases(path);
+        recording
+    }
+
+    /// Make sure everything bought during the recording is still for sale,
+    /// so a recording from an older catalog fails here rather than midway.
+    fn check_purchases(&self, path: &Path) {
+        let ladders = ShopCatalog::load().ladders.len();
+        let baits = BaitCatalog::load().baits.len();
+
+        for (frame, recorded) in self.frames.iter().enumerate() {
+            if let Some(ladder) = recorded.purchases.iter().find(|&&i| i >= ladders) {
+                panic!(
+                    "recording {} buys from shop ladder {ladder} on frame {frame}, but there are only {ladders}",
+                    path.display()
+                );
+            }
+            if let Some(bait) = recorded.bait_purchases.iter().find(|&&i| i >= baits) {
+                panic!(
+                    "recording {} buys bait {bait} on frame {frame}, but there are only {baits}",
+                    path.display()
+                );
+            }
+        }
+    }
+
+    pub fn write(&self, path: &Path) {
+        let contents = match ron::to_string(self) {
+            Ok(contents) => contents,
+            Err(e) => {
+                error!("failed to serialize recording: {e}");
+                return;
+            }
+        };
+
+        match fs::write(path, contents) {
+            Ok(()) => info!(
+                "recorded {} frames to {}",
+                self.frames.len(),
+                path.display()
+            ),
+            Err(e) => error!("failed to write recording {}: {e}", path.display()),
+        }
+    }
+}
+
+/// Records the session from the moment the game starts until it is left by
+/// restarting or quitting to the title, and writes it to `path` when the game
+/// exits.
+#[derive(Resource)]
+pub struct Recorder {
+    path: PathBuf,
+    recording: Option<Recording>,
+    finished: bool,
+}
+
+impl Recorder {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            recording: None,
+            finished: false,
+        }
+    }
+}
+
+/// Plays a recording back in place of the keyboard, starting the game
+/// straight away. Control returns to the keyboard once it runs out.
+#[derive(Resource)]
+pub struct Replay {
+    recording: Recording,
+    started: bool,
+    /// The recorded frame being played this frame.
+    frame: Option<usize>,
+}
+
+impl Replay {
+    pub fn new(recording: Recording) -> Self {
+        Self {
+            recording,
+            started: false,
+            frame: None,
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.recording.seed
+    }
+}
+
+fn start_recording(
+    mut recorder: ResMut<Recorder>,
+    rng: Res<GameRng>,
+    loaded: Option<Res<LoadedSave>>,
+) {
+    if recorder.recording.is_some() {
+        return;
+    }
+
+    recorder.recording = Some(Recording {
+        version: RECORDING_VERSION,
+        seed: rng.seed(),
+        save: loaded.map(|loaded| loaded.0.clone()),
+        frames: Vec::new(),
+    });
+}
+
+fn finish_recording(mut recorder: ResMut<Recorder>) {
+    recorder.finished = true;
+}
+
+fn record_frame(
+    time: Res<Time<Real>>,
+    actions: Res<Input<GameAction>>,
+    mut ev_buy_upgrade: EventReader<BuyUpgradeEvent>,
+    mut ev_buy_bait: EventReader<BuyBaitEvent>,
+    mut ev_resume: EventReader<ResumeEvent>,
+    mut ev_travel: EventReader<TravelEvent>,
+    mut recorder: ResMut<Recorder>,
+) {
+    if recorder.finished {
+        return;
+    }
+    let Some(recording) = &mut recorder.recording else {
+        return;
+    };
+
+    recording.frames.push(RecordedFrame {
+        delta: time.delta(),
+        actions: GameAction::ALL
+            .into_iter()
+            .filter(|action| actions.pressed(*action))
+            .collect(),
+        purchases: ev_buy_upgrade.read().map(|ev| ev.ladder).collect(),
+        bait_purchases: ev_buy_bait.read().map(|ev| ev.bait).collect(),
+        resumed: ev_resume.read().count() > 0,
+        travelled: ev_travel.read().last().map(|ev| ev.location),
+    });
+}
+
+fn write_recording(mut ev_app_exit: EventReader<AppExit>, recorder: Res<Recorder>) {
+    if ev_app_exit.is_empty() {
+        return;
+    }
+    ev_app_exit.clear();
+
+    if let Some(recording) = &recorder.recording {
+        recording.write(&recorder.path);
+    }
+}
+
+/// Start the game just as the Play button would have.
+fn start_replay(
+    mut commands: Commands,
+    mut replay: ResMut<Replay>,
+    mut game_state: ResMut<NextState<GameState>>,
+    mut menu_state: ResMut<NextState<MenuState>>,
+) {
+    if replay.started {
+        return;
+    }
+    replay.started = true;
+
+    if let Some(save) = replay.recording.save.clone() {
+        save.apply(&mut commands);
+    }
+    game_state.set(GameState::Game);
+    menu_state.set(MenuState::Disabled);
+}
+
+/// Move on to the next recorded frame, stepping time by exactly as much as
+/// it took when it was recorded.
+fn next_replay_frame(
+    mut commands: Commands,
+    mut replay: ResMut<Replay>,
+    mut time_strategy: ResMut<TimeUpdateStrategy>,
+) {
+    if !replay.started {
+        return;
+    }
+
+    let frame = replay.frame.map_or(0, |frame| frame + 1);
+
+    let Some(recorded) = replay.recording.frames.get(frame) else {
+        info!("replay finished after {frame} frames");
+        *time_strategy = TimeUpdateStrategy::Automatic;
+        commands.remove_resource::<Replay>();
+        return;
+    };
+
+    *time_strategy = TimeUpdateStrategy::ManualDuration(recorded.delta);
+    replay.frame = Some(frame);
+}
+
+fn play_replay_frame(
+    replay: Res<Replay>,
+    mut actions: ResMut<Input<GameAction>>,
+    mut ev_buy_upgrade: EventWriter<BuyUpgradeEvent>,
+    mut ev_buy_bait: EventWriter<BuyBaitEvent>,
+    mut ev_resume: EventWriter<ResumeEvent>,
+    mut ev_travel: EventWriter<TravelEvent>,
+) {
+    let Some(recorded) = replay.frame.map(|frame| &replay.recording.frames[frame]) else {
+        return;
+    };
+
+    set_actions(&mut actions, |action| recorded.actions.contains(&action));
+
+    for ladder in &recorded.purchases {
+        ev_buy_upgrade.send(BuyUpgradeEvent { ladder: *ladder });
+    }
+    for bait in &recorded.bait_purchases {
+        ev_buy_bait.send(BuyBaitEvent { bait: *bait });
+    }
+    if recorded.resumed {
+        ev_resume.send(ResumeEvent);
+    }
+    if let Some(location) = recorded.travelled {
+        ev_travel.send(TravelEvent { location });
+    }
+}
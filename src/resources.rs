@@ -1,11 +1,25 @@
-use bevy::prelude::*;
+use bevy::{asset::io::file::FileAssetReader, prelude::*};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::{components::Weight, fish::FishVariant};
 
+/// A fish in the boat's hold.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct StoredFish {
+    pub variant: FishVariant,
+    pub weight: Weight,
+    /// How far down it took the hook.
+    pub depth: f32,
+    /// 1 when caught, falling to 0 as it spoils.
+    pub freshness: f32,
+}
+
 #[derive(Resource, Default)]
 pub struct PlayerFishStored {
-    pub fish: Vec<(FishVariant, Weight)>,
+    pub fish: Vec<StoredFish>,
 }
 
 #[derive(Resource, Default)]
@@ -13,22 +27,87 @@ pub struct AliveFish {
     pub count: u32,
 }
 
+/// How loud sound effects and music play, from 0 to 1.
+#[derive(Resource, Debug, Component, PartialEq, Clone, Copy)]
+pub struct Volume(pub f32);
+
 #[derive(Resource)]
 pub struct PortStorage {
     pub weight: f32,
+    pub coins: u32,
     pub fish: HashMap<FishVariant, u32>,
-    // Goals
-    pub weight_two: bool,
-    pub weight_five: bool,
 }
 
 impl Default for PortStorage {
     fn default() -> Self {
         Self {
             weight: 0.,
+            coins: 0,
             fish: FishVariant::iterator().map(|key| (*key, 0)).collect(),
-            weight_two: false,
-            weight_five: false,
         }
     }
 }
+
+/// The one source of randomness for gameplay, so a session can be replayed
+/// from its seed.
+///
+/// Each system draws from its own named stream of the seed. That keeps one
+/// system's rolls from shifting another's when Bevy runs them in a
+/// different order.
+#[derive(Resource)]
+pub struct GameRng {
+    seed: u64,
+    streams: HashMap<&'static str, ChaCha8Rng>,
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            streams: HashMap::new(),
+        }
+    }
+
+    /// Use the given seed, or pick a fresh one when there isn't one.
+    pub fn from_seed_or_entropy(seed: Option<u64>) -> Self {
+        Self::new(seed.unwrap_or_else(rand::random))
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Start every stream over from the seed, so each session played with it
+    /// generates the same world.
+    pub fn restart(&mut self) {
+        self.streams.clear();
+    }
+
+    pub fn stream(&mut self, name: &'static str) -> &mut ChaCha8Rng {
+        let seed = self.seed;
+        self.streams.entry(name).or_insert_with(|| {
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            rng.set_stream(stream_id(name));
+            rng
+        })
+    }
+}
+
+/// FNV-1a, which unlike `DefaultHasher` is guaranteed to give the same
+/// stream for a name on every build.
+fn stream_id(name: &str) -> u64 {
+    name.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Read a RON data file from the `assets` folder. These hold game tuning
+/// rather than art, so they're read up front instead of through the
+/// `AssetServer`.
+pub fn load_data_file<T: DeserializeOwned>(path: &str) -> T {
+    let path = FileAssetReader::get_base_path().join("assets").join(path);
+    let contents = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("failed to read {}: {e}", path.display()));
+
+    ron::from_str(&contents).unwrap_or_else(|e| panic!("failed to parse {}: {e}", path.display()))
+}
@@ -1,13 +1,23 @@
-use crate::components::{CameraShake, DecayTimer, Weight};
+use crate::components::{CameraShake, DecayTimer, Hitbox, Invincibility, Weight};
 use crate::{
+    actions::GameAction,
+    bait::Lure,
     components::{Acceleration, Velocity},
-    events::{BoatCollisionEvent, FishCollisionWithRodEvent, TrashCollisionEvent},
-    fish::Fish,
+    events::{BoatCollisionEvent, FishCollisionWithRodEvent, LineSnapEvent, TrashCollisionEvent},
+    fish::{Fish, FishStamina, FishState, FishVariant},
+    game::{OnGameScreen, WATER_SURFACE},
+    location::Location,
     player::{Boat, Player},
+    resources::GameRng,
+    spatial::SpatialGrid,
     trash::Trash,
+    weather::Weather,
     GameState::Game,
+    GameplaySet,
 };
 use bevy::{prelude::*, sprite::collide_aabb::collide};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 
 #[derive(Default)]
 pub struct RodProperties {
@@ -15,7 +25,7 @@ pub struct RodProperties {
     pub pull: f32,
 }
 
-#[derive(Component, Clone, Copy, Eq, PartialEq)]
+#[derive(Component, Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
 pub enum RodVariant {
     StickWithString,
     TwigAndTwineTackler,
@@ -53,6 +63,20 @@ enum RodState {
 #[derive(Component)]
 pub struct Rod;
 
+/// Strain on the line while a fish is fighting it. The line snaps if the
+/// tension stays maxed out for too long.
+#[derive(Component, Default)]
+pub struct LineTension {
+    /// 0 is slack, 1 is at breaking point.
+    pub current: f32,
+    /// How long the tension has been at breaking point.
+    pub overload: f32,
+    /// How far the fish has dragged the hook sideways from the boat.
+    pub offset: f32,
+    /// Which way the fish is running, -1 or 1.
+    pub side: f32,
+}
+
 pub struct RodPlugin;
 
 #[derive(Component)]
@@ -63,28 +87,44 @@ struct Line;
 
 impl Plugin for RodPlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<BoatCollisionEvent>().add_systems(
-            Update,
-            (
-                cast_rod,
-                rod_movement,
-                check_for_boat_collisions,
-                check_for_fish_collisions,
-                check_for_trash_collisions,
-                update_line,
-                despawn_player_text,
-            )
-                .run_if(in_state(Game)),
-        );
+        app.add_event::<BoatCollisionEvent>()
+            .add_event::<LineSnapEvent>()
+            .add_systems(
+                Update,
+                (
+                    cast_rod,
+                    rod_movement,
+                    fight_fish,
+                    check_for_boat_collisions,
+                    check_for_fish_collisions,
+                    check_for_trash_collisions,
+                    update_line,
+                    despawn_player_text,
+                )
+                    .chain()
+                    .in_set(GameplaySet::Rod)
+                    .run_if(in_state(Game)),
+            );
     }
 }
 
 const ROD_MOVEMENT_DOWN: f32 = 75.0;
+const HOOK_SIZE: Vec2 = Vec2::new(16., 16.);
+/// How hard a fish pulls per square root kg of its weight.
+const FISH_PULL_PER_ROOT_KG: f32 = 15.;
+const FISH_MAX_OFFSET: f32 = 150.;
+/// How many times its own pull a rod holds before the line starts to strain.
+const LINE_HOLD: f32 = 2.;
+const TENSION_RISE: f32 = 1.5;
+const TENSION_RELAX: f32 = 0.8;
+const LINE_SNAP_TIME: f32 = 1.5;
+/// How long a fish that nibbled the bait without biting leaves it alone.
+const NIBBLE_COOLDOWN: f32 = 1.;
 
 fn cast_rod(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-    keyboard_input: Res<Input<KeyCode>>,
+    actions: Res<Input<GameAction>>,
     rod: Query<&Rod>,
     player_position: Query<&Transform, With<Player>>,
 ) {
@@ -95,9 +135,10 @@ fn cast_rod(
         return;
     }
 
-    if keyboard_input.just_pressed(KeyCode::Down) {
+    if actions.just_pressed(GameAction::Cast) {
         commands.spawn((
             Rod,
+            OnGameScreen,
             RodState::Idle,
             SpriteBundle {
                 texture: asset_server.load("fish_hook.png"),
@@ -108,8 +149,10 @@ fn cast_rod(
                 },
                 ..default()
             },
+            Hitbox(HOOK_SIZE),
             Velocity(Vec3::new(0., -ROD_MOVEMENT_DOWN, 0.)),
             Acceleration(Vec3::splat(0.)),
+            LineTension::default(),
             LineToPlayer,
         ));
 
@@ -127,16 +170,22 @@ fn cast_rod(
                 ..default()
             },
             Line,
+            OnGameScreen,
         ));
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn update_line(
     mut line_query: Query<(&mut Transform, &mut Sprite), With<Line>>,
-    rod_query: Query<&Transform, (With<Rod>, Without<Player>, Without<Line>)>,
+    rod_query: Query<(&Transform, &LineTension), (With<Rod>, Without<Player>, Without<Line>)>,
     player_query: Query<&Transform, (With<Player>, Without<Rod>, Without<Line>)>,
 ) {
-    if let (Ok((mut line_transform, mut line_sprite)), Ok(rod_transform), Ok(player_transform)) = (
+    if let (
+        Ok((mut line_transform, mut line_sprite)),
+        Ok((rod_transform, tension)),
+        Ok(player_transform),
+    ) = (
         line_query.get_single_mut(),
         rod_query.get_single(),
         player_query.get_single(),
@@ -149,6 +198,8 @@ fn update_line(
 
         line_transform.translation = midpoint;
         line_sprite.custom_size = Some(Vec2::new(1.0, length));
+        // Redden the line as it nears breaking point
+        line_sprite.color = Color::rgb(tension.current.clamp(0., 1.), 0., 0.);
 
         let direction = rod_transform.translation - player_transform.translation;
         let angle = direction.y.atan2(direction.x);
@@ -156,17 +207,23 @@ fn update_line(
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn check_for_boat_collisions(
     mut commands: Commands,
     rod_query: Query<(Entity, &Transform), (With<Rod>, Without<Player>)>,
     line_query: Query<Entity, With<Line>>,
     player_query: Query<&Transform, With<Player>>,
-    boat_query: Query<(&Transform, &Handle<Image>), With<Boat>>,
+    boat_query: Query<(&Transform, &Hitbox), With<Boat>>,
+    fish_query: Query<&FishState, With<Fish>>,
     mut ev_boat_collision: EventWriter<BoatCollisionEvent>,
-    assets: Res<Assets<Image>>,
 ) {
+    // The line stays out until a hooked fish has been tired out
+    if fish_query.iter().any(|state| *state == FishState::Hooked) {
+        return;
+    }
+
     let player = player_query.single();
-    let (boat, image) = boat_query.single();
+    let (boat, boat_hitbox) = boat_query.single();
 
     let (rod_entity, rod) = match rod_query.get_single() {
         Ok((rod_entity, rod)) => (rod_entity, rod),
@@ -178,21 +235,21 @@ fn check_for_boat_collisions(
         Err(_) => return,
     };
 
-    if collide(
-        // Child (boat) position is relative to parent (player) so we must
-        // combine their translations to get the boats world translation
-        player.translation + boat.translation,
-        assets
-            .get(image)
-            .expect("boat to always have an available image")
-            .size()
-            .as_vec2()
-            * boat.scale.truncate(),
+    // Child (boat) position is relative to parent (player) so we must
+    // combine their translations to get the boats world translation
+    let boat_position = player.translation + boat.translation;
+    let touching = collide(
+        boat_position,
+        boat_hitbox.0 * boat.scale.truncate(),
         rod.translation,
         rod.scale.truncate(),
     )
-    .is_none()
-    {
+    .is_some();
+    // A fish that tired out at the surface can leave the hook off to one
+    // side, to be reeled up past the hull rather than into it
+    let past_boat = rod.translation.y >= boat_position.y;
+
+    if !touching && !past_boat {
         return;
     }
 
@@ -217,30 +274,39 @@ pub fn despawn_player_text(
     }
 }
 
+/// Hook the first fish to touch the hook. A bare hook catches anything, but
+/// a fish only takes bait with the chance it has of biting it.
+#[allow(clippy::type_complexity)]
 fn check_for_fish_collisions(
-    assets: Res<Assets<Image>>,
     mut commands: Commands,
-    fish_query: Query<(Entity, &Transform, &Weight), With<Fish>>,
+    fish_query: Query<
+        (Entity, &Transform, &Hitbox, &Weight, &FishVariant),
+        (With<Fish>, Without<Invincibility>),
+    >,
     mut collision_events: EventWriter<FishCollisionWithRodEvent>,
-    mut rod_query: Query<(&Transform, &mut RodState, &Handle<Image>), With<Rod>>,
+    mut rod_query: Query<(&Transform, &mut RodState, &Hitbox, Option<&Lure>), With<Rod>>,
     camera_query: Query<(Entity, &Transform), (With<Camera2d>, Without<Fish>)>,
+    grid: Res<SpatialGrid<Fish>>,
+    mut rng: ResMut<GameRng>,
 ) {
-    let (rod, mut state, image) = match rod_query.get_single_mut() {
-        Ok((rod, state, image)) => (rod, state, image),
+    let (rod, mut state, rod_hitbox, lure) = match rod_query.get_single_mut() {
+        Ok((rod, state, rod_hitbox, lure)) => (rod, state, rod_hitbox, lure),
         Err(_) => return,
     };
+    let rod_size = rod_hitbox.0 * rod.scale.truncate();
+
+    for fish in grid.nearby(rod.translation.truncate(), rod_size) {
+        let Ok((fish, fish_transform, fish_hitbox, fish_weight, fish_variant)) =
+            fish_query.get(fish)
+        else {
+            continue;
+        };
 
-    for (fish, fish_transform, fish_weight) in &fish_query {
         if collide(
             fish_transform.translation,
-            fish_transform.scale.truncate(),
+            fish_hitbox.0 * fish_transform.scale.truncate(),
             rod.translation,
-            assets
-                .get(image)
-                .expect("boat to always have an available image")
-                .size()
-                .as_vec2()
-                * rod.scale.truncate(),
+            rod_size,
         )
         .is_none()
         {
@@ -249,6 +315,22 @@ fn check_for_fish_collisions(
 
         match *state {
             RodState::Idle => {
+                // A bare hook takes whatever swims into it
+                if let Some(lure) = lure {
+                    if !rng
+                        .stream("bite")
+                        .gen_bool(lure.bite_chance(*fish_variant).clamp(0., 1.) as f64)
+                    {
+                        commands.entity(fish).insert(Invincibility {
+                            invincibility_timer: Timer::from_seconds(
+                                NIBBLE_COOLDOWN,
+                                TimerMode::Once,
+                            ),
+                        });
+                        continue;
+                    }
+                }
+
                 collision_events.send(FishCollisionWithRodEvent { fish });
                 *state = RodState::Reeling;
 
@@ -266,27 +348,27 @@ fn check_for_fish_collisions(
 }
 
 fn check_for_trash_collisions(
-    assets: Res<Assets<Image>>,
-    mut rod_query: Query<(&Transform, &mut RodState, &Handle<Image>), With<Rod>>,
-    trash_query: Query<&Transform, With<Trash>>,
+    mut rod_query: Query<(&Transform, &mut RodState, &Hitbox), With<Rod>>,
+    trash_query: Query<(&Transform, &Hitbox), With<Trash>>,
+    grid: Res<SpatialGrid<Trash>>,
     mut collision_events: EventWriter<TrashCollisionEvent>,
 ) {
-    let (rod, mut state, image) = match rod_query.get_single_mut() {
-        Ok((rod, state, image)) => (rod, state, image),
+    let (rod, mut state, rod_hitbox) = match rod_query.get_single_mut() {
+        Ok((rod, state, rod_hitbox)) => (rod, state, rod_hitbox),
         Err(_) => return,
     };
+    let rod_size = rod_hitbox.0 * rod.scale.truncate();
+
+    for trash in grid.nearby(rod.translation.truncate(), rod_size) {
+        let Ok((trash_transform, trash_hitbox)) = trash_query.get(trash) else {
+            continue;
+        };
 
-    for trash_transform in &trash_query {
         if collide(
             trash_transform.translation,
-            trash_transform.scale.truncate(),
+            trash_hitbox.0 * trash_transform.scale.truncate(),
             rod.translation,
-            assets
-                .get(image)
-                .expect("boat to always have an available image")
-                .size()
-                .as_vec2()
-                * rod.scale.truncate(),
+            rod_size,
         )
         .is_none()
         {
@@ -303,28 +385,43 @@ fn check_for_trash_collisions(
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn rod_movement(
     time: Res<Time>,
-    keyboard_input: Res<Input<KeyCode>>,
+    actions: Res<Input<GameAction>>,
     mut rod_query: Query<
-        (&mut Transform, &mut Acceleration, &mut Velocity),
+        (
+            &mut Transform,
+            &mut Acceleration,
+            &mut Velocity,
+            &LineTension,
+        ),
         (With<Rod>, Without<Player>),
     >,
     player_query: Query<(&Transform, &RodVariant), With<Player>>,
+    weather: Res<Weather>,
+    location: Res<Location>,
 ) {
     let (player, rod_stats) = player_query.single();
-    let (mut transform, mut acceleration, mut velocity) = match rod_query.get_single_mut() {
-        Ok((transform, acceleration, velocity)) => (transform, acceleration, velocity),
+    let (mut transform, mut acceleration, mut velocity, tension) = match rod_query.get_single_mut()
+    {
+        Ok((transform, acceleration, velocity, tension)) => {
+            (transform, acceleration, velocity, tension)
+        }
         Err(_) => return,
     };
 
     let rod_stats = rod_stats.get_rod_properties();
+    let reach = rod_stats.length.min(location.0.depth);
 
-    // Keep rod x aligned with player
-    transform.translation.x = player.translation.x;
+    // Keep rod x aligned with player, bar however far a fish has dragged it
+    // or the wind has carried it
+    transform.translation.x = player.translation.x
+        + tension.offset
+        + weather.hook_drift(-transform.translation.y / rod_stats.length);
 
     // Move rod
-    if keyboard_input.just_pressed(KeyCode::Space) && acceleration.0.y < 150. {
+    if actions.just_pressed(GameAction::Reel) && acceleration.0.y < 150. {
         acceleration.0.y += rod_stats.pull * 1.5;
     }
 
@@ -338,10 +435,179 @@ fn rod_movement(
         acceleration.0.y = 0.;
     }
 
-    // Ensure rod doesn't move further than its length
-    if transform.translation.y < -rod_stats.length {
+    // Ensure rod doesn't move further than its length, or through the bottom
+    if transform.translation.y < -reach {
         velocity.0 = Vec3::splat(0.);
     } else {
         velocity.0 = Vec3::new(0., -ROD_MOVEMENT_DOWN, 0.);
     }
 }
+
+/// Pit a hooked fish against the rod. The fish drags the hook away and down
+/// while it has the stamina to, and reeling against it strains the line.
+/// Heavier fish pull harder, and stronger rods take more strain to snap.
+#[allow(clippy::type_complexity)]
+fn fight_fish(
+    time: Res<Time>,
+    mut ev_line_snap: EventWriter<LineSnapEvent>,
+    mut rod_query: Query<
+        (
+            &mut Transform,
+            &Acceleration,
+            &mut LineTension,
+            &mut RodState,
+        ),
+        (With<Rod>, Without<Fish>),
+    >,
+    mut fish_query: Query<(&Weight, &mut FishStamina, &mut FishState), With<Fish>>,
+    player_query: Query<&RodVariant, With<Player>>,
+    location: Res<Location>,
+    mut rng: ResMut<GameRng>,
+) {
+    let Ok((mut transform, acceleration, mut tension, mut rod_state)) = rod_query.get_single_mut()
+    else {
+        return;
+    };
+    let rod_stats = player_query.single().get_rod_properties();
+    let reach = rod_stats.length.min(location.0.depth);
+    let delta = time.delta_seconds();
+
+    let Some((weight, mut stamina, mut fish_state)) = fish_query
+        .iter_mut()
+        .find(|(_, _, state)| **state == FishState::Hooked)
+    else {
+        // Nothing fighting, so let the line settle back under the boat
+        tension.current = (tension.current - TENSION_RELAX * delta).max(0.);
+        tension.overload = 0.;
+        tension.offset -= tension.offset * delta.min(1.);
+        tension.side = 0.;
+        return;
+    };
+
+    if tension.side == 0. {
+        tension.side = if rng.stream("fight").gen() { 1. } else { -1. };
+    }
+
+    let fish_pull = fish_pull(weight.current, &stamina);
+    let reeling = acceleration.0.y > 0.;
+    let line_fully_out = transform.translation.y <= -reach;
+
+    // The fish runs away from the boat and dives
+    let offset = (tension.offset + tension.side * fish_pull * 0.5 * delta)
+        .clamp(-FISH_MAX_OFFSET, FISH_MAX_OFFSET);
+    transform.translation.x += offset - tension.offset;
+    tension.offset = offset;
+    // and won't be pulled out of the water until it has tired
+    transform.translation.y = (transform.translation.y - fish_pull * delta)
+        .max(-reach)
+        .min(WATER_SURFACE);
+
+    match fight_step(
+        &mut tension,
+        &mut stamina,
+        weight.current,
+        rod_stats.pull,
+        reeling || line_fully_out,
+        delta,
+    ) {
+        Some(FightOutcome::Landed) => *fish_state = FishState::Caught,
+        Some(FightOutcome::Snapped) => {
+            ev_line_snap.send_default();
+            *rod_state = RodState::Idle;
+            *tension = LineTension::default();
+        }
+        None => {}
+    }
+}
+
+/// How a fight came to an end.
+#[derive(Debug, PartialEq)]
+enum FightOutcome {
+    /// The fish tired out.
+    Landed,
+    /// The line broke.
+    Snapped,
+}
+
+/// How hard a hooked fish pulls, easing off as it tires.
+fn fish_pull(weight: f32, stamina: &FishStamina) -> f32 {
+    FISH_PULL_PER_ROOT_KG * weight.sqrt() * stamina.fraction()
+}
+
+/// Play `delta` seconds of a fight. While `straining` the line tightens if
+/// the fish pulls harder than the rod can hold, and slackens if not.
+fn fight_step(
+    tension: &mut LineTension,
+    stamina: &mut FishStamina,
+    weight: f32,
+    rod_pull: f32,
+    straining: bool,
+    delta: f32,
+) -> Option<FightOutcome> {
+    let strain = fish_pull(weight, stamina) / (rod_pull * LINE_HOLD);
+
+    if straining {
+        tension.current = (tension.current + (strain - 1.) * TENSION_RISE * delta).max(0.);
+    } else {
+        tension.current = (tension.current - TENSION_RELAX * delta).max(0.);
+    }
+
+    // Fish tire faster the harder they have to fight, and the harder they
+    // pull against the rod
+    stamina.current -= (0.5 + tension.current) * (rod_pull / 100.) * (1. + strain) * delta;
+    if stamina.current <= 0. {
+        return Some(FightOutcome::Landed);
+    }
+
+    if tension.current >= 1. {
+        tension.current = 1.;
+        tension.overload += delta;
+    } else {
+        tension.overload = 0.;
+    }
+
+    (tension.overload >= LINE_SNAP_TIME).then_some(FightOutcome::Snapped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reel against a fish of `weight` kg the whole way, without ever
+    /// easing off, until the fight is over.
+    fn fight(rod: RodVariant, weight: f32) -> FightOutcome {
+        let pull = rod.get_rod_properties().pull;
+        let mut tension = LineTension::default();
+        let mut stamina = FishStamina::from_weight(&Weight { current: weight });
+
+        // Ten minutes at 60 fps
+        (0..36_000)
+            .find_map(|_| fight_step(&mut tension, &mut stamina, weight, pull, true, 1. / 60.))
+            .expect("every fight to end")
+    }
+
+    #[test]
+    fn top_rod_lands_the_heaviest_fish() {
+        assert_eq!(
+            fight(RodVariant::CarbonCaster9000, 500.),
+            FightOutcome::Landed
+        );
+    }
+
+    #[test]
+    fn first_rod_snaps_on_the_heaviest_fish() {
+        assert_eq!(
+            fight(RodVariant::StickWithString, 500.),
+            FightOutcome::Snapped
+        );
+    }
+
+    #[test]
+    fn any_rod_lands_a_small_fish() {
+        assert_eq!(fight(RodVariant::StickWithString, 1.), FightOutcome::Landed);
+        assert_eq!(
+            fight(RodVariant::CarbonCaster9000, 1.),
+            FightOutcome::Landed
+        );
+    }
+}
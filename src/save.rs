@@ -0,0 +1,826 @@
+use crate::{
+    bait::{BaitInventory, BaitKind},
+    clock::GameClock,
+    components::{FishStorage, Weight},
+    contracts::ContractBoard,
+    events::{BuyBaitEvent, DepositFishEvent, UpgradeEvent},
+    fish::FishVariant,
+    journal::FishJournal,
+    location::{set_location, Location, LocationKind},
+    player::{BoatSpeed, Player, DEFAULT_BOAT_SPEED},
+    replay::Replay,
+    resources::{PlayerFishStored, PortStorage, StoredFish},
+    rod::RodVariant,
+    spoilage::{IceBox, DEFAULT_ICE_BOX},
+    GameState::Game,
+    GameplaySet,
+};
+use bevy::{ecs::system::SystemParam, prelude::*};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs, path::PathBuf};
+
+/// Bump this whenever [`SaveData`] changes shape, and teach [`migrate`] how
+/// to bring the previous version forward a step.
+pub const SAVE_VERSION: u32 = 9;
+
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                apply_loaded_save
+                    .before(GameplaySet::Player)
+                    .run_if(resource_exists::<LoadedSave>()),
+                // Replays shouldn't overwrite the player's real progress, and
+                // saving after gameplay catches this frame's purchases
+                autosave
+                    .after(GameplaySet::Speech)
+                    .run_if(not(resource_exists::<Replay>())),
+            )
+                .run_if(in_state(Game)),
+        );
+    }
+}
+
+/// Everything needed to pick a game back up where it was left off.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SaveData {
+    pub version: u32,
+    pub port_weight: f32,
+    pub coins: u32,
+    pub port_fish: HashMap<FishVariant, u32>,
+    pub player_fish: Vec<StoredFish>,
+    pub rod: RodVariant,
+    pub boat_speed: f32,
+    pub storage_current: f32,
+    pub storage_max: f32,
+    pub bait: HashMap<BaitKind, u32>,
+    pub selected_bait: Option<BaitKind>,
+    pub location: LocationKind,
+    pub journal: FishJournal,
+    /// Day the game had got to.
+    pub day: u32,
+    /// Time of day it was.
+    pub hour: f32,
+    pub contracts: ContractBoard,
+    pub ice_box: f32,
+}
+
+/// Saves from before the time of day was kept, when every load started
+/// the day over.
+#[derive(Deserialize)]
+struct SaveDataV8 {
+    port_weight: f32,
+    coins: u32,
+    port_fish: HashMap<FishVariant, u32>,
+    player_fish: Vec<StoredFish>,
+    rod: RodVariant,
+    boat_speed: f32,
+    storage_current: f32,
+    storage_max: f32,
+    bait: HashMap<BaitKind, u32>,
+    selected_bait: Option<BaitKind>,
+    location: LocationKind,
+    journal: FishJournal,
+    day: u32,
+    contracts: ContractBoard,
+    ice_box: f32,
+}
+
+impl From<SaveDataV8> for SaveData {
+    fn from(save: SaveDataV8) -> Self {
+        SaveData {
+            version: 9,
+            port_weight: save.port_weight,
+            coins: save.coins,
+            port_fish: save.port_fish,
+            player_fish: save.player_fish,
+            rod: save.rod,
+            boat_speed: save.boat_speed,
+            storage_current: save.storage_current,
+            storage_max: save.storage_max,
+            bait: save.bait,
+            selected_bait: save.selected_bait,
+            location: save.location,
+            journal: save.journal,
+            day: save.day,
+            hour: GameClock::default().hour,
+            contracts: save.contracts,
+            ice_box: save.ice_box,
+        }
+    }
+}
+
+/// A fish in the hold before it could spoil.
+#[derive(Deserialize)]
+struct StoredFishV7 {
+    variant: FishVariant,
+    weight: Weight,
+    depth: f32,
+}
+
+/// Saves from before the catch spoiled.
+#[derive(Deserialize)]
+struct SaveDataV7 {
+    port_weight: f32,
+    coins: u32,
+    port_fish: HashMap<FishVariant, u32>,
+    player_fish: Vec<StoredFishV7>,
+    rod: RodVariant,
+    boat_speed: f32,
+    storage_current: f32,
+    storage_max: f32,
+    bait: HashMap<BaitKind, u32>,
+    selected_bait: Option<BaitKind>,
+    location: LocationKind,
+    journal: FishJournal,
+    day: u32,
+    contracts: ContractBoard,
+}
+
+impl From<SaveDataV7> for SaveDataV8 {
+    fn from(save: SaveDataV7) -> Self {
+        SaveDataV8 {
+            port_weight: save.port_weight,
+            coins: save.coins,
+            port_fish: save.port_fish,
+            player_fish: save
+                .player_fish
+                .into_iter()
+                .map(|fish| StoredFish {
+                    variant: fish.variant,
+                    weight: fish.weight,
+                    depth: fish.depth,
+                    freshness: 1.,
+                })
+                .collect(),
+            rod: save.rod,
+            boat_speed: save.boat_speed,
+            storage_current: save.storage_current,
+            storage_max: save.storage_max,
+            bait: save.bait,
+            selected_bait: save.selected_bait,
+            location: save.location,
+            journal: save.journal,
+            day: save.day,
+            contracts: save.contracts,
+            ice_box: DEFAULT_ICE_BOX,
+        }
+    }
+}
+
+/// Saves from before the contract board, when the hold didn't note how deep
+/// its fish were caught.
+#[derive(Deserialize)]
+struct SaveDataV6 {
+    port_weight: f32,
+    coins: u32,
+    port_fish: HashMap<FishVariant, u32>,
+    player_fish: Vec<(FishVariant, Weight)>,
+    rod: RodVariant,
+    boat_speed: f32,
+    storage_current: f32,
+    storage_max: f32,
+    bait: HashMap<BaitKind, u32>,
+    selected_bait: Option<BaitKind>,
+    location: LocationKind,
+    journal: FishJournal,
+    day: u32,
+}
+
+impl From<SaveDataV6> for SaveDataV7 {
+    fn from(save: SaveDataV6) -> Self {
+        SaveDataV7 {
+            port_weight: save.port_weight,
+            coins: save.coins,
+            port_fish: save.port_fish,
+            player_fish: save
+                .player_fish
+                .into_iter()
+                // Too late to know, so these won't count towards deep
+                // contracts
+                .map(|(variant, weight)| StoredFishV7 {
+                    variant,
+                    weight,
+                    depth: 0.,
+                })
+                .collect(),
+            rod: save.rod,
+            boat_speed: save.boat_speed,
+            storage_current: save.storage_current,
+            storage_max: save.storage_max,
+            bait: save.bait,
+            selected_bait: save.selected_bait,
+            location: save.location,
+            journal: save.journal,
+            day: save.day,
+            contracts: ContractBoard::default(),
+        }
+    }
+}
+
+/// Saves from before achievements took over from the goals kept with them.
+/// The goals are still in older saves, but go unread.
+#[derive(Deserialize)]
+struct SaveDataV5 {
+    port_weight: f32,
+    coins: u32,
+    port_fish: HashMap<FishVariant, u32>,
+    player_fish: Vec<(FishVariant, Weight)>,
+    rod: RodVariant,
+    boat_speed: f32,
+    storage_current: f32,
+    storage_max: f32,
+    bait: HashMap<BaitKind, u32>,
+    selected_bait: Option<BaitKind>,
+    location: LocationKind,
+    journal: FishJournal,
+    day: u32,
+}
+
+impl From<SaveDataV5> for SaveDataV6 {
+    fn from(save: SaveDataV5) -> Self {
+        SaveDataV6 {
+            port_weight: save.port_weight,
+            coins: save.coins,
+            port_fish: save.port_fish,
+            player_fish: save.player_fish,
+            rod: save.rod,
+            boat_speed: save.boat_speed,
+            storage_current: save.storage_current,
+            storage_max: save.storage_max,
+            bait: save.bait,
+            selected_bait: save.selected_bait,
+            location: save.location,
+            journal: save.journal,
+            day: save.day,
+        }
+    }
+}
+
+/// Saves from before the fish journal.
+#[derive(Deserialize)]
+struct SaveDataV4 {
+    port_weight: f32,
+    coins: u32,
+    port_fish: HashMap<FishVariant, u32>,
+    player_fish: Vec<(FishVariant, Weight)>,
+    rod: RodVariant,
+    boat_speed: f32,
+    storage_current: f32,
+    storage_max: f32,
+    bait: HashMap<BaitKind, u32>,
+    selected_bait: Option<BaitKind>,
+    location: LocationKind,
+}
+
+impl From<SaveDataV4> for SaveDataV5 {
+    fn from(save: SaveDataV4) -> Self {
+        SaveDataV5 {
+            port_weight: save.port_weight,
+            coins: save.coins,
+            port_fish: save.port_fish,
+            player_fish: save.player_fish,
+            rod: save.rod,
+            boat_speed: save.boat_speed,
+            storage_current: save.storage_current,
+            storage_max: save.storage_max,
+            bait: save.bait,
+            selected_bait: save.selected_bait,
+            location: save.location,
+            // Nothing caught before was written down
+            journal: FishJournal::default(),
+            day: 1,
+        }
+    }
+}
+
+/// Saves from before travelling between locations.
+#[derive(Deserialize)]
+struct SaveDataV3 {
+    port_weight: f32,
+    coins: u32,
+    port_fish: HashMap<FishVariant, u32>,
+    player_fish: Vec<(FishVariant, Weight)>,
+    rod: RodVariant,
+    boat_speed: f32,
+    storage_current: f32,
+    storage_max: f32,
+    bait: HashMap<BaitKind, u32>,
+    selected_bait: Option<BaitKind>,
+}
+
+impl From<SaveDataV3> for SaveDataV4 {
+    fn from(save: SaveDataV3) -> Self {
+        SaveDataV4 {
+            port_weight: save.port_weight,
+            coins: save.coins,
+            port_fish: save.port_fish,
+            player_fish: save.player_fish,
+            rod: save.rod,
+            boat_speed: save.boat_speed,
+            storage_current: save.storage_current,
+            storage_max: save.storage_max,
+            bait: save.bait,
+            selected_bait: save.selected_bait,
+            // Everything before was fished at the reef
+            location: LocationKind::Reef,
+        }
+    }
+}
+
+/// Saves from before bait.
+#[derive(Deserialize)]
+struct SaveDataV2 {
+    port_weight: f32,
+    coins: u32,
+    port_fish: HashMap<FishVariant, u32>,
+    player_fish: Vec<(FishVariant, Weight)>,
+    rod: RodVariant,
+    boat_speed: f32,
+    storage_current: f32,
+    storage_max: f32,
+}
+
+impl From<SaveDataV2> for SaveDataV3 {
+    fn from(save: SaveDataV2) -> Self {
+        SaveDataV3 {
+            port_weight: save.port_weight,
+            coins: save.coins,
+            port_fish: save.port_fish,
+            player_fish: save.player_fish,
+            rod: save.rod,
+            boat_speed: save.boat_speed,
+            storage_current: save.storage_current,
+            storage_max: save.storage_max,
+            bait: HashMap::new(),
+            selected_bait: None,
+        }
+    }
+}
+
+/// Saves from before coins and the port shop.
+#[derive(Deserialize)]
+struct SaveDataV1 {
+    port_weight: f32,
+    port_fish: HashMap<FishVariant, u32>,
+    player_fish: Vec<(FishVariant, Weight)>,
+    rod: RodVariant,
+    storage_current: f32,
+    storage_max: f32,
+}
+
+impl From<SaveDataV1> for SaveDataV2 {
+    fn from(save: SaveDataV1) -> Self {
+        SaveDataV2 {
+            port_weight: save.port_weight,
+            coins: 0,
+            port_fish: save.port_fish,
+            player_fish: save.player_fish,
+            rod: save.rod,
+            boat_speed: DEFAULT_BOAT_SPEED,
+            storage_current: save.storage_current,
+            storage_max: save.storage_max,
+        }
+    }
+}
+
+/// Only the version is read up front so older saves can be routed through
+/// [`migrate`] before being parsed as the current [`SaveData`].
+#[derive(Deserialize)]
+struct SaveHeader {
+    version: u32,
+}
+
+/// A save the session was started from. The player's gear waits in here
+/// until the player has been spawned.
+#[derive(Resource)]
+pub struct LoadedSave(pub SaveData);
+
+impl SaveData {
+    pub fn path() -> Option<PathBuf> {
+        data_path("save.ron")
+    }
+
+    pub fn exists() -> bool {
+        Self::path().is_some_and(|path| path.exists())
+    }
+
+    pub fn load() -> Option<Self> {
+        let path = Self::path()?;
+        let contents = fs::read_to_string(&path)
+            .map_err(|e| error!("failed to read save {}: {e}", path.display()))
+            .ok()?;
+
+        migrate(&contents)
+            .map_err(|e| error!("failed to load save {}: {e}", path.display()))
+            .ok()
+    }
+
+    pub fn write(&self) {
+        write_data_file("save.ron", self);
+    }
+
+    /// Throw the save away, for when the player starts over.
+    pub fn delete() {
+        if let Some(path) = Self::path().filter(|path| path.exists()) {
+            if let Err(e) = fs::remove_file(&path) {
+                error!("failed to delete save {}: {e}", path.display());
+            }
+        }
+    }
+
+    /// Restore the saved resources, and queue the player's gear to be
+    /// applied once they have been spawned.
+    pub fn apply(self, commands: &mut Commands) {
+        set_location(commands, self.location);
+        commands.insert_resource(self.journal.clone());
+        commands.insert_resource(self.contracts.clone());
+        commands.insert_resource(GameClock {
+            day: self.day,
+            hour: self.hour,
+        });
+        commands.insert_resource(LoadedSave(self.clone()));
+        commands.insert_resource(PortStorage {
+            weight: self.port_weight,
+            coins: self.coins,
+            fish: self.port_fish,
+        });
+        commands.insert_resource(PlayerFishStored {
+            fish: self.player_fish,
+        });
+        commands.insert_resource(BaitInventory {
+            counts: self.bait,
+            selected: self.selected_bait,
+        });
+    }
+}
+
+/// Where a file that outlives the session, like the save, is kept.
+pub fn data_path(file: &str) -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("gone-fish").join(file))
+}
+
+/// Write `value` to `file` in the data directory. Failing to is logged
+/// rather than fatal, as the game can carry on without it.
+pub fn write_data_file<T: Serialize>(file: &str, value: &T) {
+    let Some(path) = data_path(file) else {
+        warn!("no data directory available, {file} will not be saved");
+        return;
+    };
+
+    let contents = match to_ron(value) {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!("failed to serialize {file}: {e}");
+            return;
+        }
+    };
+
+    if let Some(dir) = path.parent() {
+        if let Err(e) = fs::create_dir_all(dir) {
+            error!("failed to create data directory {}: {e}", dir.display());
+            return;
+        }
+    }
+
+    if let Err(e) = fs::write(&path, contents) {
+        error!("failed to write {}: {e}", path.display());
+    }
+}
+
+fn to_ron<T: Serialize>(value: &T) -> ron::Result<String> {
+    ron::ser::to_string_pretty(value, Default::default())
+}
+
+fn migrate(contents: &str) -> Result<SaveData, ron::error::SpannedError> {
+    let SaveHeader { version } = ron::from_str(contents)?;
+
+    if version > SAVE_VERSION {
+        warn!("save is from a newer version ({version}), loading it anyway");
+    }
+
+    let mut save = VersionedSave::parse(version, contents)?;
+    loop {
+        save = match save {
+            VersionedSave::Current(save) => return Ok(save),
+            older => older.step(),
+        };
+    }
+}
+
+/// A save as it was written, in whichever version's shape that was.
+enum VersionedSave {
+    V1(SaveDataV1),
+    V2(SaveDataV2),
+    V3(SaveDataV3),
+    V4(SaveDataV4),
+    V5(SaveDataV5),
+    V6(SaveDataV6),
+    V7(SaveDataV7),
+    V8(SaveDataV8),
+    Current(SaveData),
+}
+
+impl VersionedSave {
+    fn parse(version: u32, contents: &str) -> Result<Self, ron::error::SpannedError> {
+        Ok(match version {
+            1 => Self::V1(ron::from_str(contents)?),
+            2 => Self::V2(ron::from_str(contents)?),
+            3 => Self::V3(ron::from_str(contents)?),
+            4 => Self::V4(ron::from_str(contents)?),
+            5 => Self::V5(ron::from_str(contents)?),
+            6 => Self::V6(ron::from_str(contents)?),
+            7 => Self::V7(ron::from_str(contents)?),
+            8 => Self::V8(ron::from_str(contents)?),
+            _ => Self::Current(ron::from_str(contents)?),
+        })
+    }
+
+    /// Bring the save forward by one version.
+    fn step(self) -> Self {
+        match self {
+            Self::V1(save) => Self::V2(save.into()),
+            Self::V2(save) => Self::V3(save.into()),
+            Self::V3(save) => Self::V4(save.into()),
+            Self::V4(save) => Self::V5(save.into()),
+            Self::V5(save) => Self::V6(save.into()),
+            Self::V6(save) => Self::V7(save.into()),
+            Self::V7(save) => Self::V8(save.into()),
+            Self::V8(save) => Self::Current(save.into()),
+            Self::Current(save) => Self::Current(save),
+        }
+    }
+}
+
+fn apply_loaded_save(
+    mut commands: Commands,
+    loaded: Res<LoadedSave>,
+    mut player_query: Query<
+        (
+            &mut FishStorage,
+            &mut RodVariant,
+            &mut BoatSpeed,
+            &mut IceBox,
+        ),
+        With<Player>,
+    >,
+) {
+    let Ok((mut storage, mut rod, mut boat_speed, mut ice_box)) = player_query.get_single_mut()
+    else {
+        return;
+    };
+
+    let LoadedSave(save) = &*loaded;
+    *rod = save.rod;
+    boat_speed.0 = save.boat_speed;
+    ice_box.0 = save.ice_box;
+    FishStorage::update_storage(save.storage_current, Some(save.storage_max), &mut storage);
+
+    commands.remove_resource::<LoadedSave>();
+}
+
+/// Everything a save is taken from.
+#[derive(SystemParam)]
+pub struct Session<'w, 's> {
+    pub port: Res<'w, PortStorage>,
+    pub player_fish: Res<'w, PlayerFishStored>,
+    pub bait: Res<'w, BaitInventory>,
+    pub journal: Res<'w, FishJournal>,
+    pub clock: Res<'w, GameClock>,
+    pub location: Res<'w, Location>,
+    pub contracts: Res<'w, ContractBoard>,
+    pub player_query: Query<
+        'w,
+        's,
+        (
+            &'static FishStorage,
+            &'static RodVariant,
+            &'static BoatSpeed,
+            &'static IceBox,
+        ),
+        With<Player>,
+    >,
+}
+
+impl Session<'_, '_> {
+    /// Everything about the game as it stands.
+    pub fn snapshot(&self) -> SaveData {
+        let (storage, rod, boat_speed, ice_box) = self.player_query.single();
+
+        SaveData {
+            version: SAVE_VERSION,
+            port_weight: self.port.weight,
+            coins: self.port.coins,
+            port_fish: self.port.fish.clone(),
+            player_fish: self.player_fish.fish.clone(),
+            rod: *rod,
+            boat_speed: boat_speed.0,
+            storage_current: storage.current,
+            storage_max: storage.max,
+            bait: self.bait.counts.clone(),
+            selected_bait: self.bait.selected,
+            location: self.location.0.kind,
+            journal: self.journal.clone(),
+            day: self.clock.day,
+            hour: self.clock.hour,
+            contracts: self.contracts.clone(),
+            ice_box: ice_box.0,
+        }
+    }
+}
+
+fn autosave(
+    mut ev_deposit: EventReader<DepositFishEvent>,
+    mut ev_upgrade: EventReader<UpgradeEvent>,
+    mut ev_buy_bait: EventReader<BuyBaitEvent>,
+    session: Session,
+) {
+    if ev_deposit.is_empty() && ev_upgrade.is_empty() && ev_buy_bait.is_empty() {
+        return;
+    }
+    ev_deposit.clear();
+    ev_upgrade.clear();
+    ev_buy_bait.clear();
+
+    session.snapshot().write();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        components::Weight,
+        contracts::{Contract, ContractGoal},
+        journal::{CatchDate, JournalEntry},
+        location::LocationCatalog,
+    };
+    use bevy::ecs::system::SystemState;
+
+    /// A save written by each old version, oldest first.
+    const FIXTURES: [&str; 8] = [
+        include_str!("../tests/fixtures/saves/v1.ron"),
+        include_str!("../tests/fixtures/saves/v2.ron"),
+        include_str!("../tests/fixtures/saves/v3.ron"),
+        include_str!("../tests/fixtures/saves/v4.ron"),
+        include_str!("../tests/fixtures/saves/v5.ron"),
+        include_str!("../tests/fixtures/saves/v6.ron"),
+        include_str!("../tests/fixtures/saves/v7.ron"),
+        include_str!("../tests/fixtures/saves/v8.ron"),
+    ];
+
+    #[test]
+    fn migrates_every_old_version() {
+        for (version, contents) in (1..).zip(FIXTURES) {
+            let save = migrate(contents).expect("every fixture to migrate");
+
+            assert_eq!(save.version, SAVE_VERSION, "v{version}");
+            assert_eq!(save.port_weight, 12.5, "v{version}");
+            assert_eq!(save.port_fish[&FishVariant::One], 3, "v{version}");
+            assert_eq!(save.player_fish.len(), 2, "v{version}");
+            assert_eq!(
+                save.player_fish[1].variant,
+                FishVariant::Three,
+                "v{version}"
+            );
+            assert_eq!(save.player_fish[1].weight.current, 4.25, "v{version}");
+            assert_eq!(save.rod, RodVariant::TwigAndTwineTackler, "v{version}");
+            assert_eq!(save.storage_current, 5.75, "v{version}");
+            assert_eq!(save.storage_max, 20., "v{version}");
+            assert_eq!(save.hour, GameClock::default().hour, "v{version}");
+
+            // Whatever a version didn't keep yet is filled in as a new game
+            // would have it
+            if version >= 2 {
+                assert_eq!(save.coins, 40, "v{version}");
+                assert_eq!(save.boat_speed, 180., "v{version}");
+            } else {
+                assert_eq!(save.coins, 0, "v{version}");
+                assert_eq!(save.boat_speed, DEFAULT_BOAT_SPEED, "v{version}");
+            }
+            if version >= 3 {
+                assert_eq!(save.bait[&BaitKind::Worm], 4, "v{version}");
+                assert_eq!(save.selected_bait, Some(BaitKind::Worm), "v{version}");
+            } else {
+                assert!(save.bait.is_empty(), "v{version}");
+                assert_eq!(save.selected_bait, None, "v{version}");
+            }
+            if version >= 4 {
+                assert_eq!(save.location, LocationKind::Lake, "v{version}");
+            } else {
+                assert_eq!(save.location, LocationKind::Reef, "v{version}");
+            }
+            if version >= 5 {
+                let entry = save.journal.get(FishVariant::One);
+                assert_eq!(entry.map(|entry| entry.caught), Some(2), "v{version}");
+                assert_eq!(save.day, 3, "v{version}");
+            } else {
+                assert!(save.journal.entries.is_empty(), "v{version}");
+                assert_eq!(save.day, 1, "v{version}");
+            }
+            if version >= 7 {
+                assert_eq!(save.player_fish[0].depth, 120., "v{version}");
+                assert_eq!(save.contracts.contracts.len(), 1, "v{version}");
+            } else {
+                assert_eq!(save.player_fish[0].depth, 0., "v{version}");
+                assert!(save.contracts.contracts.is_empty(), "v{version}");
+            }
+            if version >= 8 {
+                assert_eq!(save.player_fish[0].freshness, 0.5, "v{version}");
+                assert_eq!(save.ice_box, 2., "v{version}");
+            } else {
+                assert_eq!(save.player_fish[0].freshness, 1., "v{version}");
+                assert_eq!(save.ice_box, DEFAULT_ICE_BOX, "v{version}");
+            }
+        }
+    }
+
+    #[test]
+    fn snapshot_survives_being_written_and_loaded() {
+        let mut world = World::new();
+        world.insert_resource(PortStorage {
+            weight: 8.,
+            coins: 125,
+            ..default()
+        });
+        world.insert_resource(PlayerFishStored {
+            fish: vec![StoredFish {
+                variant: FishVariant::Four,
+                weight: Weight { current: 2.5 },
+                depth: 210.,
+                freshness: 0.75,
+            }],
+        });
+        world.insert_resource(BaitInventory {
+            counts: HashMap::from([(BaitKind::Jig, 2)]),
+            selected: Some(BaitKind::Jig),
+        });
+        world.insert_resource(FishJournal {
+            entries: HashMap::from([(
+                FishVariant::Four,
+                JournalEntry {
+                    caught: 1,
+                    heaviest: 2.5,
+                    first_caught: CatchDate { day: 2, hour: 9. },
+                },
+            )]),
+        });
+        world.insert_resource(GameClock { day: 4, hour: 18.5 });
+        world.insert_resource(Location(
+            LocationCatalog::load().get(LocationKind::Ocean).clone(),
+        ));
+        let mut contracts = ContractBoard::default();
+        contracts.contracts.push(Contract {
+            goal: ContractGoal::Deep { depth: 200. },
+            count: 3,
+            delivered: 1,
+            reward: 60,
+            due: 90.,
+        });
+        world.insert_resource(contracts);
+        world.spawn((
+            Player,
+            FishStorage {
+                current: 2.5,
+                max: 40.,
+            },
+            RodVariant::FiberFusion,
+            BoatSpeed(210.),
+            IceBox(1.5),
+        ));
+
+        let mut session = SystemState::<Session>::new(&mut world);
+        let snapshot = session.get(&world).snapshot();
+        let contents = to_ron(&snapshot).expect("every snapshot to serialize");
+        let save = migrate(&contents).expect("every snapshot to load back");
+
+        assert_eq!(save.version, SAVE_VERSION);
+        assert_eq!(save.port_weight, 8.);
+        assert_eq!(save.coins, 125);
+        assert_eq!(save.port_fish, snapshot.port_fish);
+        assert_eq!(save.player_fish.len(), 1);
+        assert_eq!(save.player_fish[0].variant, FishVariant::Four);
+        assert_eq!(save.player_fish[0].depth, 210.);
+        assert_eq!(save.player_fish[0].freshness, 0.75);
+        assert_eq!(save.rod, RodVariant::FiberFusion);
+        assert_eq!(save.boat_speed, 210.);
+        assert_eq!(save.storage_current, 2.5);
+        assert_eq!(save.storage_max, 40.);
+        assert_eq!(save.bait, HashMap::from([(BaitKind::Jig, 2)]));
+        assert_eq!(save.selected_bait, Some(BaitKind::Jig));
+        assert_eq!(save.location, LocationKind::Ocean);
+        assert_eq!(
+            save.journal
+                .get(FishVariant::Four)
+                .map(|entry| entry.caught),
+            Some(1)
+        );
+        assert_eq!(save.day, 4);
+        assert_eq!(save.hour, 18.5);
+        assert_eq!(save.contracts.contracts.len(), 1);
+        assert_eq!(save.contracts.contracts[0].delivered, 1);
+        assert_eq!(save.ice_box, 1.5);
+    }
+}
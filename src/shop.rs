@@ -0,0 +1,392 @@
+use crate::{
+    bait::{BaitCatalog, BaitInventory},
+    components::FishStorage,
+    despawn_screen,
+    events::{BuyBaitEvent, BuyUpgradeEvent, PortCollisionEvent, UpgradeEvent},
+    menu::NORMAL_BUTTON,
+    player::{BoatSpeed, Player},
+    replay::Replay,
+    resources::{load_data_file, PortStorage},
+    rod::RodVariant,
+    spoilage::IceBox,
+    GameState::Game,
+    GameplaySet, TEXT_COLOR,
+};
+use bevy::prelude::*;
+use serde::Deserialize;
+
+pub struct ShopPlugin;
+
+impl Plugin for ShopPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ShopCatalog::load())
+            .add_event::<BuyUpgradeEvent>()
+            .add_event::<BuyBaitEvent>()
+            .add_systems(
+                Update,
+                (
+                    toggle_shop,
+                    // Mouse input isn't recorded, so keep it out of replays
+                    shop_action.run_if(not(resource_exists::<Replay>())),
+                    buy_upgrades,
+                    buy_bait,
+                )
+                    .chain()
+                    .in_set(GameplaySet::Shop)
+                    .run_if(in_state(Game)),
+            )
+            .add_systems(OnExit(Game), despawn_screen::<ShopUI>);
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub enum Upgrade {
+    Rod(RodVariant),
+    /// New hold capacity in kg.
+    Hold(f32),
+    /// New top speed for the boat in px/s.
+    Boat(f32),
+    /// New rate the catch spoils at, 1 without ice.
+    Ice(f32),
+}
+
+impl Upgrade {
+    /// Whether this would improve on the gear the player already has.
+    fn improves(
+        self,
+        rod: &RodVariant,
+        storage: &FishStorage,
+        boat: &BoatSpeed,
+        ice_box: &IceBox,
+    ) -> bool {
+        match self {
+            Upgrade::Rod(variant) => variant > *rod,
+            Upgrade::Hold(max) => max > storage.max,
+            Upgrade::Boat(speed) => speed > boat.0,
+            Upgrade::Ice(rate) => rate < ice_box.0,
+        }
+    }
+
+    fn apply(
+        self,
+        rod: &mut RodVariant,
+        storage: &mut FishStorage,
+        boat: &mut BoatSpeed,
+        ice_box: &mut IceBox,
+    ) {
+        match self {
+            Upgrade::Rod(variant) => *rod = variant,
+            Upgrade::Hold(max) => storage.max = max,
+            Upgrade::Boat(speed) => boat.0 = speed,
+            Upgrade::Ice(rate) => ice_box.0 = rate,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ShopItem {
+    pub name: String,
+    pub cost: u32,
+    pub upgrade: Upgrade,
+}
+
+/// A run of upgrades for one piece of gear, cheapest first.
+#[derive(Deserialize, Debug)]
+pub struct ShopLadder {
+    pub name: String,
+    pub tiers: Vec<ShopItem>,
+}
+
+impl ShopLadder {
+    /// The next tier the player can buy, if they haven't maxed this ladder out.
+    pub fn next_tier(
+        &self,
+        rod: &RodVariant,
+        storage: &FishStorage,
+        boat: &BoatSpeed,
+        ice_box: &IceBox,
+    ) -> Option<&ShopItem> {
+        self.tiers
+            .iter()
+            .find(|tier| tier.upgrade.improves(rod, storage, boat, ice_box))
+    }
+
+    /// Buy the next tier if it can be afforded, returning whether anything
+    /// was bought.
+    pub fn buy_next(
+        &self,
+        coins: &mut u32,
+        rod: &mut RodVariant,
+        storage: &mut FishStorage,
+        boat: &mut BoatSpeed,
+        ice_box: &mut IceBox,
+    ) -> bool {
+        let Some(tier) = self.next_tier(rod, storage, boat, ice_box) else {
+            return false;
+        };
+
+        if *coins < tier.cost {
+            return false;
+        }
+
+        *coins -= tier.cost;
+        tier.upgrade.apply(rod, storage, boat, ice_box);
+        true
+    }
+}
+
+/// Everything for sale at the port, loaded from `assets/data/shop.ron`.
+#[derive(Resource, Deserialize, Debug)]
+pub struct ShopCatalog {
+    pub ladders: Vec<ShopLadder>,
+}
+
+impl ShopCatalog {
+    pub const PATH: &'static str = "data/shop.ron";
+
+    pub fn load() -> Self {
+        load_data_file(Self::PATH)
+    }
+}
+
+#[derive(Component)]
+struct ShopUI;
+
+/// Buys the next tier of the ladder at this index in the catalog.
+#[derive(Component)]
+struct ShopButton(usize);
+
+/// Buys a pack of the bait at this index in the bait catalog.
+#[derive(Component)]
+struct BaitButton(usize);
+
+const UNAFFORDABLE_TEXT_COLOR: Color = Color::rgb(0.5, 0.5, 0.5);
+
+/// Open the shop while the boat is docked at the port, and keep it showing
+/// the latest coins and upgrades.
+#[allow(clippy::too_many_arguments)]
+fn toggle_shop(
+    mut commands: Commands,
+    mut ev_port_collision: EventReader<PortCollisionEvent>,
+    shop_query: Query<Entity, With<ShopUI>>,
+    catalog: Res<ShopCatalog>,
+    bait_catalog: Res<BaitCatalog>,
+    port: Res<PortStorage>,
+    inventory: Res<BaitInventory>,
+    player_query: Query<(&RodVariant, &FishStorage, &BoatSpeed, &IceBox), With<Player>>,
+    asset_server: Res<AssetServer>,
+) {
+    let docked = ev_port_collision.read().count() > 0;
+    let shop = shop_query.get_single().ok();
+
+    if let Some(shop) = shop {
+        if docked && !port.is_changed() && !inventory.is_changed() {
+            return;
+        }
+
+        commands.entity(shop).despawn_recursive();
+    }
+
+    if docked {
+        spawn_shop(
+            &mut commands,
+            &asset_server,
+            (&catalog, &bait_catalog),
+            (&port, &inventory),
+            player_query.single(),
+        );
+    }
+}
+
+fn spawn_shop(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    (catalog, bait_catalog): (&ShopCatalog, &BaitCatalog),
+    (port, inventory): (&PortStorage, &BaitInventory),
+    (rod, storage, boat, ice_box): (&RodVariant, &FishStorage, &BoatSpeed, &IceBox),
+) {
+    let button_style = Style {
+        width: Val::Px(420.0),
+        height: Val::Px(40.0),
+        margin: UiRect::all(Val::Px(4.0)),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    };
+    let text_style = TextStyle {
+        font: asset_server.load("fonts/Pixellari.ttf"),
+        font_size: 20.0,
+        color: TEXT_COLOR,
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(10.0),
+                    left: Val::Px(10.0),
+                    padding: UiRect::all(Val::Px(10.0)),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.6).into(),
+                ..default()
+            },
+            ShopUI,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                format!("Port Shop - {} coins", port.coins),
+                TextStyle {
+                    font_size: 30.0,
+                    ..text_style.clone()
+                },
+            ));
+
+            for (index, ladder) in catalog.ladders.iter().enumerate() {
+                let Some(tier) = ladder.next_tier(rod, storage, boat, ice_box) else {
+                    parent.spawn(TextBundle::from_section(
+                        format!("{}: Fully upgraded", ladder.name),
+                        TextStyle {
+                            color: UNAFFORDABLE_TEXT_COLOR,
+                            ..text_style.clone()
+                        },
+                    ));
+                    continue;
+                };
+
+                let color = if port.coins >= tier.cost {
+                    TEXT_COLOR
+                } else {
+                    UNAFFORDABLE_TEXT_COLOR
+                };
+
+                parent
+                    .spawn((
+                        ButtonBundle {
+                            style: button_style.clone(),
+                            background_color: NORMAL_BUTTON.into(),
+                            ..default()
+                        },
+                        ShopButton(index),
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section(
+                            format!("{}: {} - {} coins", ladder.name, tier.name, tier.cost),
+                            TextStyle {
+                                color,
+                                ..text_style.clone()
+                            },
+                        ));
+                    });
+            }
+
+            for (index, bait) in bait_catalog.baits.iter().enumerate() {
+                let color = if port.coins >= bait.cost {
+                    TEXT_COLOR
+                } else {
+                    UNAFFORDABLE_TEXT_COLOR
+                };
+
+                parent
+                    .spawn((
+                        ButtonBundle {
+                            style: button_style.clone(),
+                            background_color: NORMAL_BUTTON.into(),
+                            ..default()
+                        },
+                        BaitButton(index),
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section(
+                            format!(
+                                "{} x{} ({} held) - {} coins",
+                                bait.name,
+                                bait.pack_size,
+                                inventory.count(bait.kind),
+                                bait.cost
+                            ),
+                            TextStyle {
+                                color,
+                                ..text_style.clone()
+                            },
+                        ));
+                    });
+            }
+        });
+}
+
+fn shop_action(
+    interaction_query: Query<(&Interaction, &ShopButton), Changed<Interaction>>,
+    bait_interaction_query: Query<(&Interaction, &BaitButton), Changed<Interaction>>,
+    mut ev_buy_upgrade: EventWriter<BuyUpgradeEvent>,
+    mut ev_buy_bait: EventWriter<BuyBaitEvent>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            ev_buy_upgrade.send(BuyUpgradeEvent { ladder: button.0 });
+        }
+    }
+
+    for (interaction, button) in &bait_interaction_query {
+        if *interaction == Interaction::Pressed {
+            ev_buy_bait.send(BuyBaitEvent { bait: button.0 });
+        }
+    }
+}
+
+fn buy_upgrades(
+    mut ev_buy_upgrade: EventReader<BuyUpgradeEvent>,
+    catalog: Res<ShopCatalog>,
+    mut port: ResMut<PortStorage>,
+    mut player_query: Query<
+        (
+            &mut RodVariant,
+            &mut FishStorage,
+            &mut BoatSpeed,
+            &mut IceBox,
+        ),
+        With<Player>,
+    >,
+    mut ev_upgrade: EventWriter<UpgradeEvent>,
+) {
+    for ev in ev_buy_upgrade.read() {
+        let (mut rod, mut storage, mut boat, mut ice_box) = player_query.single_mut();
+        let Some(ladder) = catalog.ladders.get(ev.ladder) else {
+            warn!("no shop ladder {} to buy from", ev.ladder);
+            continue;
+        };
+        let bought = ladder.buy_next(
+            &mut port.coins,
+            &mut rod,
+            &mut storage,
+            &mut boat,
+            &mut ice_box,
+        );
+
+        if bought {
+            ev_upgrade.send_default();
+        }
+    }
+}
+
+fn buy_bait(
+    mut ev_buy_bait: EventReader<BuyBaitEvent>,
+    catalog: Res<BaitCatalog>,
+    mut port: ResMut<PortStorage>,
+    mut inventory: ResMut<BaitInventory>,
+) {
+    for ev in ev_buy_bait.read() {
+        let Some(bait) = catalog.baits.get(ev.bait) else {
+            warn!("no bait {} to buy", ev.bait);
+            continue;
+        };
+
+        if port.coins >= bait.cost {
+            port.coins -= bait.cost;
+            inventory.add(bait.kind, bait.pack_size);
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use crate::components::Hitbox;
+use bevy::{prelude::*, utils::HashMap};
+use std::marker::PhantomData;
+
+/// Width and height of a grid cell, in px. About the distance fish look for
+/// their school over, so a neighbour search only has to check a few cells.
+pub const CELL_SIZE: f32 = 128.;
+
+#[derive(Clone, Copy, Debug)]
+struct Entry {
+    entity: Entity,
+    position: Vec2,
+}
+
+/// Where every entity with a `T` was at the start of the frame, bucketed
+/// into a uniform grid so collision and neighbour checks only look at
+/// entities close by instead of all of them.
+///
+/// Entities are bucketed by their centre. Searches widen by the largest
+/// half size inserted, so a big hitbox is still found from a neighbouring
+/// cell.
+#[derive(Resource)]
+pub struct SpatialGrid<T> {
+    cells: HashMap<IVec2, Vec<Entry>>,
+    max_half_size: Vec2,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Default for SpatialGrid<T> {
+    fn default() -> Self {
+        SpatialGrid {
+            cells: HashMap::default(),
+            max_half_size: Vec2::ZERO,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> SpatialGrid<T> {
+    /// Empty the grid, keeping the cells' allocations for the next rebuild.
+    pub fn clear(&mut self) {
+        for cell in self.cells.values_mut() {
+            cell.clear();
+        }
+        self.max_half_size = Vec2::ZERO;
+    }
+
+    pub fn insert(&mut self, entity: Entity, position: Vec2, size: Vec2) {
+        self.max_half_size = self.max_half_size.max(size.abs() / 2.);
+        self.cells
+            .entry(cell_of(position))
+            .or_default()
+            .push(Entry { entity, position });
+    }
+
+    /// Entities whose hitbox might overlap a box of `size` at `position`.
+    /// Candidates still need an exact check.
+    pub fn nearby(&self, position: Vec2, size: Vec2) -> impl Iterator<Item = Entity> + '_ {
+        self.entries_around(position, size.abs() / 2. + self.max_half_size)
+            .map(|entry| entry.entity)
+    }
+
+    /// Entities with their centre within `radius` of `position`, and where
+    /// that centre is.
+    pub fn within(&self, position: Vec2, radius: f32) -> impl Iterator<Item = (Entity, Vec2)> + '_ {
+        self.entries_around(position, Vec2::splat(radius))
+            .filter(move |entry| entry.position.distance_squared(position) <= radius * radius)
+            .map(|entry| (entry.entity, entry.position))
+    }
+
+    /// Entries with their centre inside the box `half_size` either side of
+    /// `position`. Cells are visited in a fixed order so results come back
+    /// in the same order every run.
+    fn entries_around(&self, position: Vec2, half_size: Vec2) -> impl Iterator<Item = &Entry> {
+        let min = cell_of(position - half_size);
+        let max = cell_of(position + half_size);
+
+        (min.y..=max.y)
+            .flat_map(move |y| (min.x..=max.x).map(move |x| IVec2::new(x, y)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .filter(move |entry| {
+                let offset = (entry.position - position).abs();
+                offset.x <= half_size.x && offset.y <= half_size.y
+            })
+    }
+}
+
+fn cell_of(position: Vec2) -> IVec2 {
+    (position / CELL_SIZE).floor().as_ivec2()
+}
+
+/// Refill the grid with where every `T` is now.
+pub fn rebuild_grid<T: Component>(
+    mut grid: ResMut<SpatialGrid<T>>,
+    query: Query<(Entity, &Transform, &Hitbox), With<T>>,
+) {
+    grid.clear();
+
+    for (entity, transform, hitbox) in &query {
+        grid.insert(
+            entity,
+            transform.translation.truncate(),
+            hitbox.0 * transform.scale.truncate(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Centres on, just either side of and well between cell boundaries,
+    /// on both sides of the origin.
+    const COORDS: [f32; 11] = [
+        -384., -256.5, -256., -255.5, -100., 0., 0.5, 127.5, 128., 128.5, 300.,
+    ];
+
+    fn grid_with(size: Vec2) -> (SpatialGrid<()>, Vec<Entry>) {
+        let mut grid = SpatialGrid::default();
+        let mut entries = Vec::new();
+        for (i, position) in COORDS
+            .iter()
+            .flat_map(|&x| COORDS.iter().map(move |&y| Vec2::new(x, y)))
+            .enumerate()
+        {
+            let entity = Entity::from_raw(i as u32);
+            grid.insert(entity, position, size);
+            entries.push(Entry { entity, position });
+        }
+        (grid, entries)
+    }
+
+    fn sorted(mut entities: Vec<Entity>) -> Vec<Entity> {
+        entities.sort();
+        entities
+    }
+
+    #[test]
+    fn nearby_finds_every_overlapping_hitbox() {
+        let size = Vec2::new(40., 20.);
+        let (grid, entries) = grid_with(size);
+        let query_size = Vec2::new(30., 300.);
+
+        for &x in &COORDS {
+            for &y in &COORDS {
+                let position = Vec2::new(x, y);
+                let found: Vec<Entity> = grid.nearby(position, query_size).collect();
+
+                let reach = (size + query_size) / 2.;
+                let expected = entries
+                    .iter()
+                    .filter(|entry| {
+                        let offset = (entry.position - position).abs();
+                        offset.x <= reach.x && offset.y <= reach.y
+                    })
+                    .map(|entry| entry.entity)
+                    .collect();
+
+                assert_eq!(sorted(found), sorted(expected), "around {position}");
+            }
+        }
+    }
+
+    #[test]
+    fn within_matches_a_full_scan() {
+        let (grid, entries) = grid_with(Vec2::ZERO);
+
+        for radius in [0., 0.5, 100., 128., 200.] {
+            for &x in &COORDS {
+                for &y in &COORDS {
+                    let position = Vec2::new(x, y);
+                    let found: Vec<Entity> = grid
+                        .within(position, radius)
+                        .map(|(entity, _)| entity)
+                        .collect();
+
+                    let expected = entries
+                        .iter()
+                        .filter(|entry| entry.position.distance(position) <= radius)
+                        .map(|entry| entry.entity)
+                        .collect();
+
+                    assert_eq!(
+                        sorted(found),
+                        sorted(expected),
+                        "{radius} around {position}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn cells_split_on_the_boundary() {
+        assert_eq!(cell_of(Vec2::new(0., 0.)), IVec2::new(0, 0));
+        assert_eq!(cell_of(Vec2::new(-0.5, 127.5)), IVec2::new(-1, 0));
+        assert_eq!(cell_of(Vec2::new(-128., 128.)), IVec2::new(-1, 1));
+        assert_eq!(cell_of(Vec2::new(-128.5, -256.)), IVec2::new(-2, -2));
+    }
+
+    #[test]
+    fn clear_forgets_every_entity() {
+        let (mut grid, _) = grid_with(Vec2::splat(500.));
+        grid.clear();
+
+        assert_eq!(grid.nearby(Vec2::ZERO, Vec2::splat(1000.)).count(), 0);
+        assert_eq!(grid.max_half_size, Vec2::ZERO);
+    }
+}
@@ -1,7 +1,12 @@
 use crate::components::DecayTimer;
-use crate::events::{CatchFishEvent, DepositFishEvent, UpgradeEvent, WeightLimitEvent};
+use crate::events::{
+    CatchFishEvent, ContractCompletedEvent, DepositFishEvent, LineSnapEvent, NewRecordEvent,
+    RoughWaterEvent, UpgradeEvent, WeightLimitEvent,
+};
+use crate::fish::{FishRegistry, TROPHY_COLOR};
 use crate::player::Player;
-use crate::GameState::Game;
+use crate::resources::GameRng;
+use crate::{GameState::Game, GameplaySet};
 use bevy::prelude::*;
 use rand::Rng;
 use std::time::Duration;
@@ -13,7 +18,7 @@ pub struct PlayerTextPlugin;
 
 impl Plugin for PlayerTextPlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<WeightLimitEvent>().add_systems(
+        app.add_systems(
             Update,
             (
                 despawn_player_text,
@@ -21,7 +26,13 @@ impl Plugin for PlayerTextPlugin {
                 check_for_catch_fish_collision_event,
                 check_for_fish_deposit_event,
                 check_for_upgrade_event,
+                check_for_line_snap_event,
+                check_for_rough_water_event,
+                check_for_new_record_event,
+                check_for_contract_completed_event,
             )
+                .chain()
+                .in_set(GameplaySet::Speech)
                 .run_if(in_state(Game)),
         );
     }
@@ -71,6 +82,7 @@ fn check_for_weight_limit_event(
     mut ev_weight_limit: EventReader<WeightLimitEvent>,
     asset_server: Res<AssetServer>,
     player_query: Query<Entity, With<Player>>,
+    mut rng: ResMut<GameRng>,
 ) {
     let player = player_query.single();
 
@@ -96,7 +108,7 @@ fn check_for_weight_limit_event(
     for _ in ev_weight_limit.read() {
         let text = generate_text_entity(
             &mut commands,
-            speech_options[rand::thread_rng().gen_range(0..speech_options.len() - 1)].into(),
+            speech_options[rng.stream("speech").gen_range(0..speech_options.len())].into(),
             ui_text_style.clone(),
             Vec3::new(40., 30., 0.),
             Vec3::splat(0.25),
@@ -112,6 +124,7 @@ fn check_for_upgrade_event(
     mut ev_upgrade: EventReader<UpgradeEvent>,
     asset_server: Res<AssetServer>,
     player_query: Query<Entity, With<Player>>,
+    mut rng: ResMut<GameRng>,
 ) {
     let player = player_query.single();
 
@@ -137,7 +150,7 @@ fn check_for_upgrade_event(
     for _ in ev_upgrade.read() {
         let text = generate_text_entity(
             &mut commands,
-            speech_options[rand::thread_rng().gen_range(0..speech_options.len() - 1)].into(),
+            speech_options[rng.stream("speech").gen_range(0..speech_options.len())].into(),
             ui_text_style.clone(),
             Vec3::new(75., 75., 0.),
             Vec3::splat(0.25),
@@ -148,6 +161,79 @@ fn check_for_upgrade_event(
     }
 }
 
+fn check_for_line_snap_event(
+    mut commands: Commands,
+    mut ev_line_snap: EventReader<LineSnapEvent>,
+    asset_server: Res<AssetServer>,
+    player_query: Query<Entity, With<Player>>,
+    mut rng: ResMut<GameRng>,
+) {
+    let player = player_query.single();
+
+    let ui_text_style = TextStyle {
+        font: asset_server.load("fonts/Pixellari.ttf"),
+        font_size: 40.0,
+        color: Color::WHITE,
+    };
+
+    let speech_options = [
+        "Snap! That one was too\nstrong for this line.",
+        "There goes the line...\nShould've let it tire out.",
+        "Lost it! Easy on the reel\nwhen the big ones run.",
+        "The line couldn't take it.\nNeed a stronger rod.",
+        "Argh, snapped clean through!",
+    ];
+
+    for _ in ev_line_snap.read() {
+        let text = generate_text_entity(
+            &mut commands,
+            speech_options[rng.stream("speech").gen_range(0..speech_options.len())].into(),
+            ui_text_style.clone(),
+            Vec3::new(40., 30., 0.),
+            Vec3::splat(0.25),
+            Duration::new(3, 0),
+        );
+
+        commands.entity(player).push_children(&[text]);
+    }
+}
+
+fn check_for_rough_water_event(
+    mut commands: Commands,
+    mut ev_rough_water: EventReader<RoughWaterEvent>,
+    asset_server: Res<AssetServer>,
+    player_query: Query<Entity, With<Player>>,
+    mut rng: ResMut<GameRng>,
+) {
+    let player = player_query.single();
+
+    let ui_text_style = TextStyle {
+        font: asset_server.load("fonts/Pixellari.ttf"),
+        font_size: 40.0,
+        color: Color::WHITE,
+    };
+
+    let speech_options = [
+        "Too rough out there for this\nboat. Need something faster.",
+        "The swell's too much. Better\noars might get me through.",
+        "Can't make headway past here.\nTime to upgrade the boat.",
+        "Not in this tub. The port\nsells faster boats.",
+    ];
+
+    for _ in ev_rough_water.read() {
+        let text = generate_text_entity(
+            &mut commands,
+            speech_options[rng.stream("speech").gen_range(0..speech_options.len())].into(),
+            ui_text_style.clone(),
+            Vec3::new(40., 30., 0.),
+            Vec3::splat(0.25),
+            Duration::new(3, 0),
+        );
+
+        commands.entity(player).push_children(&[text]);
+    }
+}
+
 fn check_for_catch_fish_collision_event(
     mut commands: Commands,
     mut ev_catch_fish: EventReader<CatchFishEvent>,
@@ -165,7 +251,11 @@ fn check_for_catch_fish_collision_event(
     for fish in ev_catch_fish.read() {
         let text = generate_text_entity(
             &mut commands,
-            format!("+ {:.2} kg", fish.weight.current),
+            if fish.trophy {
+                format!("Trophy!\n+ {:.2} kg", fish.weight.current)
+            } else {
+                format!("+ {:.2} kg", fish.weight.current)
+            },
             ui_text_style.clone(),
             Vec3::new(50., 15., 0.),
             Vec3::splat(0.25),
@@ -181,6 +271,7 @@ fn check_for_fish_deposit_event(
     mut deposit_fish_event: EventReader<DepositFishEvent>,
     asset_server: Res<AssetServer>,
     player_query: Query<Entity, With<Player>>,
+    mut rng: ResMut<GameRng>,
 ) {
     let player = player_query.single();
 
@@ -206,7 +297,7 @@ fn check_for_fish_deposit_event(
     for _ in deposit_fish_event.read() {
         let text = generate_text_entity(
             &mut commands,
-            speech_options[rand::thread_rng().gen_range(0..speech_options.len() - 1)].into(),
+            speech_options[rng.stream("speech").gen_range(0..speech_options.len())].into(),
             ui_text_style.clone(),
             Vec3::new(40., 30., 0.),
             Vec3::splat(0.25),
@@ -216,3 +307,62 @@ fn check_for_fish_deposit_event(
         commands.entity(player).push_children(&[text]);
     }
 }
+
+fn check_for_new_record_event(
+    mut commands: Commands,
+    mut ev_new_record: EventReader<NewRecordEvent>,
+    asset_server: Res<AssetServer>,
+    registry: Res<FishRegistry>,
+    player_query: Query<Entity, With<Player>>,
+) {
+    let player = player_query.single();
+
+    let ui_text_style = TextStyle {
+        font: asset_server.load("fonts/Pixellari.ttf"),
+        font_size: 40.0,
+        color: TROPHY_COLOR,
+    };
+
+    // Stack records broken together rather than drawing them over each other
+    for (i, ev) in ev_new_record.read().enumerate() {
+        let text = generate_text_entity(
+            &mut commands,
+            format!("New record!\n{}", ev.record.describe(&registry)),
+            ui_text_style.clone(),
+            Vec3::new(-40., 45. + i as f32 * 12., 0.),
+            Vec3::splat(0.25),
+            Duration::new(3, 0),
+        );
+
+        commands.entity(player).push_children(&[text]);
+    }
+}
+
+fn check_for_contract_completed_event(
+    mut commands: Commands,
+    mut ev_contract_completed: EventReader<ContractCompletedEvent>,
+    asset_server: Res<AssetServer>,
+    player_query: Query<Entity, With<Player>>,
+) {
+    let player = player_query.single();
+
+    let ui_text_style = TextStyle {
+        font: asset_server.load("fonts/Pixellari.ttf"),
+        font_size: 40.0,
+        color: Color::rgb(0.5, 1., 0.5),
+    };
+
+    // Above the deposit chatter, stacked if more than one was finished
+    for (i, ev) in ev_contract_completed.read().enumerate() {
+        let text = generate_text_entity(
+            &mut commands,
+            format!("Contract done!\n+{} coins", ev.contract.reward),
+            ui_text_style.clone(),
+            Vec3::new(40., 60. + i as f32 * 12., 0.),
+            Vec3::splat(0.25),
+            Duration::new(3, 0),
+        );
+
+        commands.entity(player).push_children(&[text]);
+    }
+}
@@ -0,0 +1,66 @@
+use crate::{
+    fish::FishRegistry,
+    player::Player,
+    port::check_for_port_collisions,
+    resources::{PlayerFishStored, StoredFish},
+    GameState::Game,
+    GameplaySet,
+};
+use bevy::prelude::*;
+
+/// Seconds a fish keeps in the hold without ice before it has fully
+/// spoiled.
+const SHELF_LIFE: f32 = 300.;
+/// What a fully spoiled fish still sells for, as a fraction of its price
+/// fresh.
+const SPOILED_PRICE: f32 = 0.25;
+
+/// How fast the catch in the hold spoils, 1 without any ice. Lowered by ice
+/// box upgrades.
+#[derive(Component, Clone, Copy)]
+pub struct IceBox(pub f32);
+
+pub const DEFAULT_ICE_BOX: f32 = 1.;
+
+/// What `fish` sells for at the port, less however far it has spoiled.
+pub fn sale_price(registry: &FishRegistry, fish: &StoredFish) -> u32 {
+    let fresh = registry.get(fish.variant).sale_price(&fish.weight) as f32;
+    let price = fresh * (SPOILED_PRICE + (1. - SPOILED_PRICE) * fish.freshness);
+
+    (price.round() as u32).max(1)
+}
+
+pub struct SpoilagePlugin;
+
+impl Plugin for SpoilagePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            // Settled before the catch is sold, so it's priced as it was
+            // that frame
+            spoil_catch
+                .before(check_for_port_collisions)
+                .in_set(GameplaySet::Port)
+                .run_if(in_state(Game)),
+        );
+    }
+}
+
+fn spoil_catch(
+    time: Res<Time>,
+    mut player_fish: ResMut<PlayerFishStored>,
+    player_query: Query<&IceBox, With<Player>>,
+) {
+    // Leave an empty hold untouched so it isn't marked as changed
+    if player_fish.fish.is_empty() {
+        return;
+    }
+    let Ok(ice_box) = player_query.get_single() else {
+        return;
+    };
+
+    let spoiled = time.delta_seconds() / SHELF_LIFE * ice_box.0;
+    for fish in &mut player_fish.fish {
+        fish.freshness = (fish.freshness - spoiled).max(0.);
+    }
+}
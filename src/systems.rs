@@ -1,9 +1,11 @@
 use bevy::prelude::*;
 
 use crate::{
-    components::{AnimationIndices, AnimationTimer, DecayTimer, PauseMenu},
+    actions::GameAction,
+    components::{AnimationIndices, AnimationTimer, DecayTimer},
+    events::ResumeEvent,
     menu::MenuState,
-    GameState,
+    GameState, PauseState,
 };
 
 pub fn animate_sprite(
@@ -33,53 +35,53 @@ pub fn tick_decay_timers(mut can_die_query: Query<&mut DecayTimer>, timer: ResMu
 }
 
 pub fn pause_the_game(
-    keyboard_input: Res<Input<KeyCode>>,
-    mut game_state: ResMut<State<GameState>>,
-    menu_state: ResMut<State<MenuState>>,
-    mut commands: Commands,
-    pause_menu_query: Query<Entity, With<PauseMenu>>,
-    asset_server: Res<AssetServer>,
-    camera_query: Query<Entity, With<Camera>>,
+    actions: Res<Input<GameAction>>,
+    mut pause_state: ResMut<NextState<PauseState>>,
+    mut menu_state: ResMut<NextState<MenuState>>,
 ) {
-    if *menu_state != MenuState::Disabled {
+    if actions.just_pressed(GameAction::Pause) {
+        pause_state.set(PauseState::Paused);
+        menu_state.set(MenuState::Pause);
+    }
+}
+
+/// Pressing pause again on the pause menu or sea chart resumes, but deeper in
+/// the settings it does nothing rather than dropping the player back into the
+/// game.
+pub fn resume_action(
+    actions: Res<Input<GameAction>>,
+    menu_state: Res<State<MenuState>>,
+    mut ev_resume: EventWriter<ResumeEvent>,
+) {
+    let closable = matches!(menu_state.get(), MenuState::Pause | MenuState::Map);
+    if actions.just_pressed(GameAction::Pause) && closable {
+        ev_resume.send(ResumeEvent);
+    }
+}
+
+pub fn resume_game(
+    mut ev_resume: EventReader<ResumeEvent>,
+    mut pause_state: ResMut<NextState<PauseState>>,
+    mut menu_state: ResMut<NextState<MenuState>>,
+) {
+    if ev_resume.is_empty() {
         return;
     }
+    ev_resume.clear();
 
-    let camera = camera_query.single();
+    pause_state.set(PauseState::Running);
+    menu_state.set(MenuState::Disabled);
+}
 
-    let ui_text_style = TextStyle {
-        font: asset_server.load("fonts/Pixellari.ttf"),
-        font_size: 70.0,
-        color: Color::WHITE,
-    };
+/// Stop game time, so every timer holds where it is until the game resumes.
+pub fn pause_time(mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+}
 
-    if keyboard_input.just_pressed(KeyCode::Escape) {
-        match *game_state.get() {
-            GameState::Menu => {
-                let pause_menu = pause_menu_query.single();
-                commands.entity(pause_menu).despawn();
+pub fn unpause_time(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
+}
 
-                *game_state = State::new(GameState::Game);
-            }
-            GameState::Game => {
-                *game_state = State::new(GameState::Menu);
-                let pause_text = commands
-                    .spawn((
-                        Text2dBundle {
-                            text: Text::from_sections([TextSection::new("Paused", ui_text_style)])
-                                .with_alignment(TextAlignment::Center),
-                            transform: Transform {
-                                translation: Vec3::new(0., 10., 100.),
-                                scale: Vec3::splat(1.),
-                                ..default()
-                            },
-                            ..default()
-                        },
-                        PauseMenu,
-                    ))
-                    .id();
-                commands.entity(camera).push_children(&[pause_text]);
-            }
-        }
-    }
+pub fn restart_game(mut game_state: ResMut<NextState<GameState>>) {
+    game_state.set(GameState::Game);
 }
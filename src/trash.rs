@@ -1,32 +1,39 @@
 use bevy::prelude::*;
-use rand::{distributions::Standard, prelude::Distribution, Rng};
+use rand::{distributions::WeightedIndex, prelude::Distribution, Rng};
+use serde::Deserialize;
 
 use crate::{
-    components::{Direction, Speed},
+    components::{Direction, Hitbox, Speed},
     events::TrashCollisionEvent,
+    game::OnGameScreen,
+    location::Location,
+    resources::GameRng,
+    spatial::{rebuild_grid, SpatialGrid},
+    world::WorldMap,
     GameState::Game,
+    GameplaySet,
 };
 
-#[derive(Component, Clone, Copy, Debug)]
+#[derive(Component, Clone, Copy, Debug, Eq, PartialEq, Hash, Deserialize)]
 pub enum TrashVariant {
     Newspaper,
     OldShoe,
 }
 
 impl TrashVariant {
+    pub const ALL: [TrashVariant; 2] = [TrashVariant::Newspaper, TrashVariant::OldShoe];
+
     pub fn image(self, asset_server: AssetServer) -> Handle<Image> {
         match self {
-            TrashVariant::Newspaper => asset_server.load("craftpix/objects/Catch/Box.png"),
-            TrashVariant::OldShoe => asset_server.load("craftpix/objects/Catch/Barrel.png"),
+            TrashVariant::Newspaper => asset_server.load("craftpix/objects/catch/Box.png"),
+            TrashVariant::OldShoe => asset_server.load("craftpix/objects/catch/Barrel.png"),
         }
     }
-}
 
-impl Distribution<TrashVariant> for Standard {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> TrashVariant {
-        match rng.gen_range(0..2) {
-            0 => TrashVariant::Newspaper,
-            _ => TrashVariant::OldShoe,
+    pub fn hitbox(self) -> Hitbox {
+        match self {
+            TrashVariant::Newspaper => Hitbox(Vec2::new(12., 10.)),
+            TrashVariant::OldShoe => Hitbox(Vec2::new(12., 14.)),
         }
     }
 }
@@ -40,6 +47,7 @@ struct TrashBundle {
     direction: Direction,
     speed: Speed,
     variant: TrashVariant,
+    hitbox: Hitbox,
     // This might change to a SpriteSheetBundle eventually.
     sprite: SpriteBundle,
 }
@@ -51,6 +59,7 @@ impl Default for TrashBundle {
             direction: Direction::Left,
             speed: Speed { current: 100. },
             variant: TrashVariant::Newspaper,
+            hitbox: TrashVariant::Newspaper.hitbox(),
             sprite: Default::default(),
         }
     }
@@ -64,39 +73,72 @@ pub struct TrashPlugin;
 impl Plugin for TrashPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<TrashCollisionEvent>()
+            .init_resource::<SpatialGrid<Trash>>()
             .add_systems(OnEnter(Game), setup)
-            .add_systems(Update, trash_movement.run_if(in_state(Game)));
+            .add_systems(
+                Update,
+                rebuild_grid::<Trash>
+                    .in_set(GameplaySet::Spatial)
+                    .run_if(in_state(Game)),
+            )
+            .add_systems(
+                Update,
+                trash_movement
+                    .in_set(GameplaySet::Trash)
+                    .run_if(in_state(Game)),
+            );
     }
 }
 
-pub fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+pub fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    map: Res<WorldMap>,
+    location: Res<Location>,
+    mut rng: ResMut<GameRng>,
+) {
+    let rng = rng.stream("trash");
+    // Some places are kept clean
+    let Ok(dist) = WeightedIndex::new(
+        TrashVariant::ALL
+            .iter()
+            .map(|variant| location.0.trash_weight(*variant)),
+    ) else {
+        return;
+    };
+
     for _ in 0..10 {
-        let vertical_position = rand::thread_rng().gen_range(50.0..400.);
-        let horizontal_position = rand::thread_rng().gen_range(-1800.0..1800.);
-        let trash: TrashVariant = rand::random();
-
-        commands.spawn(TrashBundle {
-            sprite: SpriteBundle {
-                texture: trash.image(asset_server.clone()),
-                transform: Transform {
-                    translation: Vec3::new(horizontal_position, -vertical_position, 5.0),
-                    scale: Vec3::splat(3.),
-                    rotation: Quat::from_rotation_z(rand::thread_rng().gen_range(0.0..360.)),
+        let vertical_position = rng.gen_range(50.0..400.);
+        let horizontal_position = rng.gen_range(map.trash.clone());
+        let trash = TrashVariant::ALL[dist.sample(rng)];
+
+        commands.spawn((
+            TrashBundle {
+                sprite: SpriteBundle {
+                    texture: trash.image(asset_server.clone()),
+                    transform: Transform {
+                        translation: Vec3::new(horizontal_position, -vertical_position, 5.0),
+                        scale: Vec3::splat(3.),
+                        rotation: Quat::from_rotation_z(rng.gen_range(0.0..360.)),
+                    },
+                    ..default()
                 },
+                speed: Speed {
+                    current: rng.gen_range(TRASH_SPEED_MIN..TRASH_SPEED_MAX),
+                },
+                direction: Direction::random_y(rng),
+                variant: trash,
+                hitbox: trash.hitbox(),
                 ..default()
             },
-            speed: Speed {
-                current: rand::thread_rng().gen_range(TRASH_SPEED_MIN..TRASH_SPEED_MAX),
-            },
-            direction: Direction::random_y(),
-            variant: trash,
-            ..default()
-        });
+            OnGameScreen,
+        ));
     }
 }
 
 pub fn trash_movement(
     time: Res<Time>,
+    map: Res<WorldMap>,
     mut trash_query: Query<(&mut Transform, &mut Direction, &Speed), With<Trash>>,
 ) {
     for (mut transform, mut direction, speed) in &mut trash_query {
@@ -112,9 +154,9 @@ pub fn trash_movement(
         }
 
         // Flip the thing when at edge
-        if transform.translation.x < -1800. {
+        if transform.translation.x < map.trash.start {
             *direction = Direction::Right;
-        } else if transform.translation.x > 1800. {
+        } else if transform.translation.x > map.trash.end {
             *direction = Direction::Left;
         }
     }
@@ -1,4 +1,15 @@
 use super::GameState;
+use crate::{
+    actions::Bindings,
+    bait::{BaitCatalog, BaitInventory},
+    clock::GameClock,
+    components::FishStorage,
+    game::OnGameScreen,
+    player::{Player, BOAT_START},
+    resources::PlayerFishStored,
+    weather::Weather,
+    world::{FishingGround, WorldMap},
+};
 use bevy::prelude::*;
 
 pub struct UIPlugin;
@@ -6,20 +17,84 @@ pub struct UIPlugin;
 impl Plugin for UIPlugin {
     fn build(&self, app: &mut App) {
         app.add_state::<GameState>()
-            .add_systems(OnEnter(GameState::Game), ui_setup);
+            .add_systems(OnEnter(GameState::Game), ui_setup)
+            .add_systems(
+                Update,
+                (
+                    update_controls_text,
+                    update_bait_text,
+                    update_storage_text,
+                    update_clock_text,
+                ),
+            );
     }
 }
 
 #[derive(Component)]
-struct StorageText;
+struct ControlsText;
 
 #[derive(Component)]
-struct ControlsText;
+struct BaitText;
+
+#[derive(Component)]
+struct StorageText;
 
 #[derive(Component)]
 struct StorageIcon;
 
-fn ui_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+#[derive(Component)]
+struct ClockText;
+
+fn bait_text(catalog: &BaitCatalog, inventory: &BaitInventory) -> String {
+    match inventory.selected {
+        Some(kind) => format!(
+            "Bait: {} x{}",
+            catalog.get(kind).name,
+            inventory.count(kind)
+        ),
+        None => "Bait: Bare hook".into(),
+    }
+}
+
+fn storage_text(storage: &FishStorage, player_fish: &PlayerFishStored) -> String {
+    let hold = format!("Hold: {:.1}/{:.0} kg", storage.current, storage.max);
+    if player_fish.fish.is_empty() {
+        return hold;
+    }
+
+    let freshness = player_fish
+        .fish
+        .iter()
+        .map(|fish| fish.freshness)
+        .sum::<f32>()
+        / player_fish.fish.len() as f32;
+    format!("{hold}, {:.0}% fresh", freshness * 100.)
+}
+
+fn clock_text(clock: &GameClock, weather: &Weather, ground: &FishingGround) -> String {
+    format!(
+        "{}\n{}\n{}",
+        clock.describe(),
+        weather.kind.name(),
+        ground.name
+    )
+}
+
+fn controls_text(bindings: &Bindings) -> String {
+    format!("{}\nMouse - Buy upgrades while docked", bindings.describe())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn ui_setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    bindings: Res<Bindings>,
+    catalog: Res<BaitCatalog>,
+    inventory: Res<BaitInventory>,
+    clock: Res<GameClock>,
+    weather: Res<Weather>,
+    map: Res<WorldMap>,
+) {
     let control_text_style = TextStyle {
         font: asset_server.load("fonts/Pixellari.ttf"),
         font_size: 20.0,
@@ -28,14 +103,17 @@ fn ui_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
 
     // UI Canvas
     commands
-        .spawn(NodeBundle {
-            style: Style {
-                width: Val::Percent(100.0),
-                height: Val::Percent(100.0),
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
                 ..default()
             },
-            ..default()
-        })
+            OnGameScreen,
+        ))
         // Controls
         .with_children(|parent| {
             parent
@@ -51,11 +129,158 @@ fn ui_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                 .with_children(|parent| {
                     parent.spawn((
                         TextBundle::from_sections([TextSection::new(
-                            "Left/Right Arrows - Move boat\nDown Arrow - Cast rod\nSpace - Jerk rod upwards\nEscape - Pause the game",
-                            control_text_style,
+                            controls_text(&bindings),
+                            control_text_style.clone(),
                         )]),
                         ControlsText,
                     ));
                 });
+        })
+        // Storage and bait
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        bottom: Val::Px(10.0),
+                        left: Val::Px(10.0),
+                        flex_direction: FlexDirection::Column,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            parent.spawn((
+                                ImageBundle {
+                                    style: Style {
+                                        width: Val::Px(32.0),
+                                        height: Val::Px(32.0),
+                                        margin: UiRect::right(Val::Px(6.0)),
+                                        ..default()
+                                    },
+                                    image: asset_server.load("craftpix/icons/Icons_05.png").into(),
+                                    ..default()
+                                },
+                                StorageIcon,
+                            ));
+                            // Filled in once the player has been spawned
+                            parent.spawn((
+                                TextBundle::from_sections([TextSection::new(
+                                    "",
+                                    control_text_style.clone(),
+                                )]),
+                                StorageText,
+                            ));
+                        });
+                    parent.spawn((
+                        TextBundle::from_sections([TextSection::new(
+                            bait_text(&catalog, &inventory),
+                            TextStyle {
+                                font_size: 30.0,
+                                ..control_text_style.clone()
+                            },
+                        )]),
+                        BaitText,
+                    ));
+                });
+        })
+        // Clock
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        bottom: Val::Px(10.0),
+                        right: Val::Px(10.0),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_sections([TextSection::new(
+                            clock_text(&clock, &weather, map.ground_at(BOAT_START)),
+                            TextStyle {
+                                font_size: 30.0,
+                                ..control_text_style
+                            },
+                        )]),
+                        ClockText,
+                    ));
+                });
         });
 }
+
+fn update_controls_text(
+    bindings: Res<Bindings>,
+    mut text_query: Query<&mut Text, With<ControlsText>>,
+) {
+    if !bindings.is_changed() {
+        return;
+    }
+
+    for mut text in &mut text_query {
+        text.sections[0].value = controls_text(&bindings);
+    }
+}
+
+fn update_bait_text(
+    catalog: Res<BaitCatalog>,
+    inventory: Res<BaitInventory>,
+    mut text_query: Query<&mut Text, With<BaitText>>,
+) {
+    if !inventory.is_changed() {
+        return;
+    }
+
+    for mut text in &mut text_query {
+        text.sections[0].value = bait_text(&catalog, &inventory);
+    }
+}
+
+fn update_storage_text(
+    player_fish: Res<PlayerFishStored>,
+    player_query: Query<&FishStorage, With<Player>>,
+    mut text_query: Query<&mut Text, With<StorageText>>,
+) {
+    let Ok(storage) = player_query.get_single() else {
+        return;
+    };
+    let hold = storage_text(storage, &player_fish);
+
+    for mut text in &mut text_query {
+        if text.sections[0].value != hold {
+            text.sections[0].value = hold.clone();
+        }
+    }
+}
+
+fn update_clock_text(
+    clock: Res<GameClock>,
+    weather: Res<Weather>,
+    map: Res<WorldMap>,
+    player_query: Query<&Transform, With<Player>>,
+    mut text_query: Query<&mut Text, With<ClockText>>,
+) {
+    let Ok(player) = player_query.get_single() else {
+        return;
+    };
+    let time = clock_text(&clock, &weather, map.ground_at(player.translation.x));
+
+    for mut text in &mut text_query {
+        // Only touch the text when it changes, so it isn't laid out
+        // again every frame
+        if text.sections[0].value != time {
+            text.sections[0].value = time.clone();
+        }
+    }
+}
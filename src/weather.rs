@@ -0,0 +1,329 @@
+use crate::{
+    clock::{Backdrop, Sky, Water},
+    fish::FishVariant,
+    game::{OnGameScreen, WATER_SURFACE},
+    location::Location,
+    player::Player,
+    resources::{load_data_file, GameRng},
+    GameState::Game,
+    GameplaySet,
+};
+use bevy::{prelude::*, utils::HashMap};
+use rand::{distributions::WeightedIndex, prelude::Distribution, Rng};
+use serde::Deserialize;
+use std::ops::Range;
+
+/// How fast the wind picks up or dies down, in px/s².
+const WIND_CHANGE_RATE: f32 = 10.;
+/// How fast waves build or calm, in px/s.
+const WAVE_CHANGE_RATE: f32 = 1.;
+/// How fast the sky darkens or clears, per second.
+const GLOOM_CHANGE_RATE: f32 = 0.05;
+/// How fast rain sets in or eases off, in drops/s².
+const RAIN_CHANGE_RATE: f32 = 40.;
+/// Raindrops a second at which rain is at its loudest.
+pub const DOWNPOUR: f32 = 400.;
+/// How far the wind carries a hook at the end of the line, in seconds of
+/// wind.
+const HOOK_DRIFT_TIME: f32 = 1.5;
+/// How fast waves roll along, in radians a second.
+const WAVE_SPEED: f32 = 1.5;
+/// How far apart wave crests are, in radians per px.
+const WAVE_LENGTH: f32 = 0.01;
+const RAIN_FALL_SPEED: f32 = 600.;
+/// How much faster than the wind rain is blown sideways.
+const RAIN_WIND_FACTOR: f32 = 2.;
+const RAIN_SIZE: Vec2 = Vec2::new(2., 12.);
+const RAIN_COLOR: Color = Color::rgba(0.75, 0.85, 1., 0.6);
+/// Half the width and height of the view at a camera scale of 1, so rain
+/// only falls where it can be seen.
+const VIEW_HALF_SIZE: Vec2 = Vec2::new(640., 360.);
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Deserialize)]
+pub enum WeatherKind {
+    #[default]
+    Clear,
+    Overcast,
+    Rain,
+    Storm,
+}
+
+impl WeatherKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            WeatherKind::Clear => "Clear",
+            WeatherKind::Overcast => "Overcast",
+            WeatherKind::Rain => "Rain",
+            WeatherKind::Storm => "Storm",
+        }
+    }
+}
+
+/// Tuning for one kind of weather, loaded from `assets/data/weather.ron`.
+#[derive(Deserialize, Debug, Clone)]
+pub struct WeatherSpec {
+    pub kind: WeatherKind,
+    /// Relative chance of this being the next weather.
+    pub chance: u32,
+    /// How long it lasts, in seconds.
+    pub duration: Range<f32>,
+    /// How hard the wind blows, in px/s, either way.
+    pub wind: Range<f32>,
+    /// Raindrops a second.
+    pub rain: f32,
+    /// How high the waves get, in px.
+    pub waves: f32,
+    /// How much darker the sky gets, from 0 to 1.
+    pub gloom: f32,
+    /// Multiplies the spawn weight of the species listed.
+    #[serde(default)]
+    pub spawn: HashMap<FishVariant, f32>,
+}
+
+impl WeatherSpec {
+    pub fn spawn_multiplier(&self, variant: FishVariant) -> f32 {
+        self.spawn.get(&variant).copied().unwrap_or(1.)
+    }
+}
+
+#[derive(Resource, Deserialize, Debug)]
+pub struct WeatherCatalog {
+    pub weathers: Vec<WeatherSpec>,
+}
+
+impl WeatherCatalog {
+    pub const PATH: &'static str = "data/weather.ron";
+
+    pub fn load() -> Self {
+        load_data_file(Self::PATH)
+    }
+
+    pub fn get(&self, kind: WeatherKind) -> &WeatherSpec {
+        self.weathers
+            .iter()
+            .find(|weather| weather.kind == kind)
+            .expect("every weather kind to have a catalog entry")
+    }
+}
+
+/// The weather right now. Every session starts out clear.
+#[derive(Resource, Debug)]
+pub struct Weather {
+    pub kind: WeatherKind,
+    /// Wind speed in px/s. Positive blows to the right.
+    pub wind: f32,
+    /// What the wind is picking up or dying down to.
+    target_wind: f32,
+    /// How high the waves are, in px, on their way to the weather's.
+    pub waves: f32,
+    /// How much darker the sky is, on its way to the weather's.
+    pub gloom: f32,
+    /// Raindrops a second, on their way to the weather's.
+    pub rain: f32,
+    /// Until the weather changes.
+    timer: Timer,
+    /// Part of a raindrop left over from the last frame.
+    rain_owed: f32,
+}
+
+impl Default for Weather {
+    fn default() -> Self {
+        Weather {
+            kind: WeatherKind::Clear,
+            wind: 0.,
+            target_wind: 0.,
+            waves: 0.,
+            gloom: 0.,
+            rain: 0.,
+            timer: Timer::from_seconds(120., TimerMode::Once),
+            rain_owed: 0.,
+        }
+    }
+}
+
+impl Weather {
+    /// Height of the waves above the water line at `x`.
+    pub fn wave_height(&self, x: f32, elapsed: f32) -> f32 {
+        self.waves * (elapsed * WAVE_SPEED + x * WAVE_LENGTH).sin()
+    }
+
+    /// Sideways drift of the hook for a line let out `fraction` of the way
+    /// to the rod's length.
+    pub fn hook_drift(&self, fraction: f32) -> f32 {
+        self.wind * HOOK_DRIFT_TIME * fraction.clamp(0., 1.)
+    }
+}
+
+#[derive(Component)]
+struct RainDrop;
+
+pub struct WeatherPlugin;
+
+impl Plugin for WeatherPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(WeatherCatalog::load())
+            .init_resource::<Weather>()
+            .add_systems(
+                Update,
+                (
+                    change_weather,
+                    settle_weather,
+                    roll_waves,
+                    gloom_sky,
+                    spawn_rain,
+                    fall_rain,
+                )
+                    .chain()
+                    .in_set(GameplaySet::Weather)
+                    .run_if(in_state(Game)),
+            );
+    }
+}
+
+fn change_weather(
+    time: Res<Time>,
+    catalog: Res<WeatherCatalog>,
+    mut weather: ResMut<Weather>,
+    mut rng: ResMut<GameRng>,
+) {
+    if !weather.timer.tick(time.delta()).finished() {
+        return;
+    }
+
+    let rng = rng.stream("weather");
+    let current = weather.kind;
+    // Always move on to something else
+    let dist = WeightedIndex::new(catalog.weathers.iter().map(|spec| {
+        if spec.kind == current {
+            0
+        } else {
+            spec.chance
+        }
+    }))
+    .expect("weather file to have more than one kind of weather");
+    let next = &catalog.weathers[dist.sample(rng)];
+
+    let direction = if rng.gen::<bool>() { -1. } else { 1. };
+    weather.kind = next.kind;
+    weather.target_wind = direction * rng.gen_range(next.wind.clone());
+    weather.timer = Timer::from_seconds(rng.gen_range(next.duration.clone()), TimerMode::Once);
+}
+
+/// Ease the wind, waves and sky into the current weather's, rather than
+/// switching them over all at once.
+fn settle_weather(time: Res<Time>, catalog: Res<WeatherCatalog>, mut weather: ResMut<Weather>) {
+    let spec = catalog.get(weather.kind);
+    let delta = time.delta_seconds();
+
+    weather.wind = approach(weather.wind, weather.target_wind, WIND_CHANGE_RATE * delta);
+    weather.waves = approach(weather.waves, spec.waves, WAVE_CHANGE_RATE * delta);
+    weather.gloom = approach(weather.gloom, spec.gloom, GLOOM_CHANGE_RATE * delta);
+    weather.rain = approach(weather.rain, spec.rain, RAIN_CHANGE_RATE * delta);
+}
+
+fn approach(current: f32, target: f32, step: f32) -> f32 {
+    current + (target - current).clamp(-step, step)
+}
+
+/// Bob the waves on the water line, and the boat with them.
+fn roll_waves(
+    time: Res<Time>,
+    weather: Res<Weather>,
+    mut wave_query: Query<&mut Transform, (With<TextureAtlasSprite>, With<Water>)>,
+    mut player_query: Query<&mut Transform, (With<Player>, Without<Water>)>,
+) {
+    let elapsed = time.elapsed_seconds();
+
+    for mut transform in &mut wave_query {
+        transform.translation.y =
+            WATER_SURFACE + weather.wave_height(transform.translation.x, elapsed);
+    }
+
+    for mut transform in &mut player_query {
+        transform.translation.y = weather.wave_height(transform.translation.x, elapsed);
+    }
+}
+
+/// Darken the sky and the horizon with the weather, over the location's
+/// own tint.
+#[allow(clippy::type_complexity)]
+fn gloom_sky(
+    weather: Res<Weather>,
+    location: Res<Location>,
+    mut sky_query: Query<&mut Sprite, Or<(With<Sky>, With<Backdrop>)>>,
+) {
+    let light = (1. - weather.gloom) * location.0.sky_tint().truncate();
+
+    for mut sprite in &mut sky_query {
+        sprite.color = Color::rgba(light.x, light.y, light.z, sprite.color.a());
+    }
+}
+
+fn spawn_rain(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut weather: ResMut<Weather>,
+    camera_query: Query<(&Transform, &OrthographicProjection), With<Camera2d>>,
+    mut rng: ResMut<GameRng>,
+) {
+    let rain = weather.rain;
+    if rain <= 0. {
+        weather.rain_owed = 0.;
+        return;
+    }
+
+    let Ok((camera, projection)) = camera_query.get_single() else {
+        return;
+    };
+
+    weather.rain_owed += rain * time.delta_seconds();
+    let drops = weather.rain_owed.floor();
+    weather.rain_owed -= drops;
+
+    let rng = rng.stream("rain");
+    let half_size = VIEW_HALF_SIZE * projection.scale;
+    let velocity = Vec2::new(weather.wind * RAIN_WIND_FACTOR, -RAIN_FALL_SPEED);
+    // Start drops upwind of the view, so they're blown across it
+    let upwind = -velocity.x / RAIN_FALL_SPEED * half_size.y * 2.;
+
+    for _ in 0..drops as u32 {
+        let x = camera.translation.x + upwind + rng.gen_range(-half_size.x..half_size.x);
+
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: RAIN_COLOR,
+                    custom_size: Some(RAIN_SIZE),
+                    ..default()
+                },
+                transform: Transform {
+                    translation: Vec3::new(x, camera.translation.y + half_size.y, 15.),
+                    rotation: Quat::from_rotation_z(
+                        velocity.y.atan2(velocity.x) + std::f32::consts::FRAC_PI_2,
+                    ),
+                    ..default()
+                },
+                ..default()
+            },
+            RainDrop,
+            OnGameScreen,
+        ));
+    }
+}
+
+fn fall_rain(
+    mut commands: Commands,
+    time: Res<Time>,
+    weather: Res<Weather>,
+    mut rain_query: Query<(Entity, &mut Transform), With<RainDrop>>,
+) {
+    let velocity = Vec3::new(weather.wind * RAIN_WIND_FACTOR, -RAIN_FALL_SPEED, 0.);
+
+    for (drop, mut transform) in &mut rain_query {
+        transform.translation += velocity * time.delta_seconds();
+
+        if transform.translation.y < WATER_SURFACE {
+            commands.entity(drop).despawn();
+        }
+    }
+}
@@ -0,0 +1,238 @@
+use crate::{
+    clock::{Backdrop, Water},
+    components::{AnimationIndices, AnimationTimer},
+    fish::FishVariant,
+    game::{OnGameScreen, WATER_SURFACE},
+    location::Location,
+    GameState::Game,
+};
+use bevy::{prelude::*, sprite::Anchor, utils::HashMap};
+use serde::Deserialize;
+use std::ops::Range;
+
+/// How far past either end of the world the water carries on, so it still
+/// reaches the edge of the view with the camera zoomed all the way out.
+const VIEW_MARGIN: f32 = 2000.;
+const WAVE_TILE_WIDTH: f32 = 48.;
+/// Top of the body of water, just under the waves.
+const WATER_TOP: f32 = -50.;
+/// How far down the bottom carries on, so it fills the view zoomed out.
+const BED_HEIGHT: f32 = 4000.;
+const BED_COLOR: Color = Color::rgb(0.25, 0.22, 0.18);
+/// Distance between the bits of scenery along the water line.
+const SCENERY_SPACING: f32 = 350.;
+
+/// A stretch of water with its own mix of fish, loaded along with its
+/// location from `assets/data/locations.ron`.
+#[derive(Deserialize, Debug, Clone)]
+pub struct FishingGround {
+    pub name: String,
+    /// How far out it reaches. It starts where the one before it ends.
+    pub end: f32,
+    /// Top speed a boat needs to make it out here, in px/s.
+    pub top_speed: f32,
+    /// Multiplies the spawn weight of the species listed.
+    #[serde(default)]
+    pub spawn: HashMap<FishVariant, f32>,
+}
+
+impl FishingGround {
+    pub fn spawn_multiplier(&self, variant: FishVariant) -> f32 {
+        self.spawn.get(&variant).copied().unwrap_or(1.)
+    }
+}
+
+/// Layout of a location's water in world units, which don't depend on the
+/// size of the window.
+#[derive(Resource, Deserialize, Debug, Clone)]
+pub struct WorldMap {
+    /// Inshore edge, behind the port, that fish turn back at.
+    pub start: f32,
+    /// Where the fishing hut stands.
+    pub port: f32,
+    /// Where the port's rubbish drifts about.
+    pub trash: Range<f32>,
+    /// From the port outwards.
+    pub grounds: Vec<FishingGround>,
+}
+
+impl WorldMap {
+    /// Far edge of the world, where the last fishing ground ends.
+    pub fn end(&self) -> f32 {
+        self.grounds
+            .last()
+            .expect("every location to have at least one fishing ground")
+            .end
+    }
+
+    pub fn ground_at(&self, x: f32) -> &FishingGround {
+        self.grounds
+            .iter()
+            .find(|ground| x < ground.end)
+            .or(self.grounds.last())
+            .expect("every location to have at least one fishing ground")
+    }
+
+    /// How far out a boat with this top speed can make it, through every
+    /// ground up to the first one it isn't fast enough for.
+    pub fn reach(&self, top_speed: f32) -> f32 {
+        self.grounds
+            .iter()
+            .take_while(|ground| ground.top_speed <= top_speed)
+            .last()
+            .map_or(self.port, |ground| ground.end)
+    }
+}
+
+pub struct WorldPlugin;
+
+impl Plugin for WorldPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(Game), setup);
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+    map: Res<WorldMap>,
+    location: Res<Location>,
+) {
+    let left = map.start - VIEW_MARGIN;
+    let right = map.end() + VIEW_MARGIN;
+
+    // A barrel marks the far edge of every fishing ground
+    for ground in &map.grounds {
+        commands.spawn((
+            SpriteBundle {
+                texture: asset_server.load("craftpix/objects/Fishbarrel3.png"),
+                transform: Transform {
+                    translation: Vec3::new(ground.end, -40., 0.),
+                    scale: Vec3::splat(2.),
+                    ..default()
+                },
+                ..default()
+            },
+            OnGameScreen,
+        ));
+    }
+
+    // Far-off hills and cloud banks standing on the water line, following
+    // the camera around like the sky
+    if let Some(backdrop) = &location.0.backdrop {
+        commands.spawn((
+            SpriteBundle {
+                texture: asset_server.load(backdrop),
+                sprite: Sprite {
+                    anchor: Anchor::BottomCenter,
+                    ..default()
+                },
+                transform: Transform {
+                    translation: Vec3::new(0., WATER_SURFACE, -19.),
+                    scale: Vec3::splat(7.),
+                    ..default()
+                },
+                ..default()
+            },
+            Backdrop,
+            OnGameScreen,
+        ));
+    }
+
+    // Reeds and the like poking out of the water, behind the waves
+    let scenery = &location.0.scenery;
+    if !scenery.is_empty() {
+        let mut x = left;
+        for image in scenery.iter().cycle() {
+            if x >= right {
+                break;
+            }
+
+            commands.spawn((
+                SpriteBundle {
+                    texture: asset_server.load(image),
+                    sprite: Sprite {
+                        anchor: Anchor::BottomCenter,
+                        ..default()
+                    },
+                    transform: Transform {
+                        translation: Vec3::new(x, WATER_SURFACE, 2.),
+                        scale: Vec3::splat(3.),
+                        ..default()
+                    },
+                    ..default()
+                },
+                OnGameScreen,
+            ));
+
+            x += SCENERY_SPACING;
+        }
+    }
+
+    // Water top
+    let texture_handle = asset_server.load("critters/water_tile.png");
+    let texture_atlas =
+        TextureAtlas::from_grid(texture_handle, Vec2::new(16., 16.), 32, 1, None, None);
+    let texture_atlas_handle = texture_atlases.add(texture_atlas);
+
+    let mut x = left;
+    while x < right {
+        let animation_indices = AnimationIndices { first: 0, last: 31 };
+
+        commands.spawn((
+            SpriteSheetBundle {
+                texture_atlas: texture_atlas_handle.clone(),
+                sprite: TextureAtlasSprite::new(animation_indices.first),
+                transform: Transform {
+                    translation: Vec3::new(x, WATER_SURFACE, 3.),
+                    scale: Vec3::splat(3.),
+                    ..default()
+                },
+                ..default()
+            },
+            animation_indices,
+            AnimationTimer(Timer::from_seconds(0.2, TimerMode::Repeating)),
+            Water,
+            OnGameScreen,
+        ));
+
+        x += WAVE_TILE_WIDTH;
+    }
+
+    // Water
+    let bottom = -location.0.depth;
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::hex("#7287D5").expect("is a valid colour."),
+                ..default()
+            },
+            transform: Transform {
+                translation: Vec3::new((left + right) / 2., (WATER_TOP + bottom) / 2., 0.),
+                scale: Vec3::new(right - left, WATER_TOP - bottom, 0.),
+                ..default()
+            },
+            ..default()
+        },
+        Water,
+        OnGameScreen,
+    ));
+
+    // Bottom
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: BED_COLOR,
+                ..default()
+            },
+            transform: Transform {
+                translation: Vec3::new((left + right) / 2., bottom - BED_HEIGHT / 2., 0.),
+                scale: Vec3::new(right - left, BED_HEIGHT, 0.),
+                ..default()
+            },
+            ..default()
+        },
+        OnGameScreen,
+    ));
+}